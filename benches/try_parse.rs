@@ -0,0 +1,70 @@
+use criterion::{criterion_group, criterion_main, Criterion, black_box};
+use biscuit_converter::Biscuit;
+
+fn bench_try_u64(c: &mut Criterion) {
+    let test_set = vec![
+        "1",
+        "1234",
+        "12345678",
+        "123456789012",
+        "1234567890123456",
+        "1234567890123456789",
+    ];
+
+    for input_str in test_set {
+        let mut group = c.benchmark_group(format!("try u64 : {}", input_str));
+        let input = input_str.as_bytes();
+        group.bench_function("parse_decimal", |b| b.iter(|| u64::parse_decimal(black_box(input)).ok()));
+        group.bench_function("try_parse_decimal", |b| b.iter(|| u64::try_parse_decimal(black_box(input))));
+        group.bench_function("std", |b| b.iter(|| black_box(input_str).parse::<u64>().ok()));
+        group.finish();
+    }
+}
+
+fn bench_try_i64(c: &mut Criterion) {
+    let test_set = vec![
+        "-1",
+        "-1234",
+        "-12345678",
+        "-123456789012",
+        "-1234567890123456",
+        "-1234567890123456789",
+    ];
+
+    for input_str in test_set {
+        let mut group = c.benchmark_group(format!("try i64 : {}", input_str));
+        let input = input_str.as_bytes();
+        group.bench_function("parse_decimal", |b| b.iter(|| i64::parse_decimal(black_box(input)).ok()));
+        group.bench_function("try_parse_decimal", |b| b.iter(|| i64::try_parse_decimal(black_box(input))));
+        group.bench_function("std", |b| b.iter(|| black_box(input_str).parse::<i64>().ok()));
+        group.finish();
+    }
+}
+
+fn bench_try_u128(c: &mut Criterion) {
+    let test_set = vec![
+        "12345678",
+        "1234567890123456",
+        "123456789012345678901234",
+        "12345678901234567890123456789012",
+        "123456789012345678901234567890123456789",
+    ];
+
+    for input_str in test_set {
+        let mut group = c.benchmark_group(format!("try u128 : {}", input_str));
+        let input = input_str.as_bytes();
+        group.bench_function("parse_decimal", |b| b.iter(|| u128::parse_decimal(black_box(input)).ok()));
+        group.bench_function("try_parse_decimal", |b| b.iter(|| u128::try_parse_decimal(black_box(input))));
+        group.bench_function("std", |b| b.iter(|| black_box(input_str).parse::<u128>().ok()));
+        group.finish();
+    }
+}
+
+criterion_group!(
+    benches,
+    bench_try_u64,
+    bench_try_i64,
+    bench_try_u128,
+);
+
+criterion_main!(benches);
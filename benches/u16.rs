@@ -4,7 +4,7 @@ use atoi::atoi;
 
 fn bench_u16(c: &mut Criterion) {
 
-    let test_set = vec![
+    let test_set = [
         "1",
         "12",
         "123",
@@ -0,0 +1,253 @@
+//! Column totals without materializing the column
+//!
+//! ```rust
+//! use biscuit_converter::aggregate::DecimalAggregator;
+//! use biscuit_converter::error::ParseIntErr;
+//!
+//! let mut volume = DecimalAggregator::<u64>::new();
+//! volume.feed_delimited(b"100\n250\n7\n", b'\n');
+//! volume.feed_delimited(b"40\nn/a\n", b'\n');
+//! assert_eq!(volume.count(), 4);
+//! assert_eq!(volume.sum(), Some(397));
+//! assert_eq!((volume.min(), volume.max()), (Some(7), Some(250)));
+//! assert_eq!(volume.first_parse_error(), Some((4, ParseIntErr::NonDecimal)));
+//! ```
+use crate::Biscuit;
+use crate::columnar::Column;
+use crate::error::ParseIntErr;
+
+/// Integers that can be summed by [`DecimalAggregator`]
+pub trait Aggregatable: Biscuit + Copy + Ord {
+    const ZERO: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_aggregatable {
+    ($($t:ty)*) => {
+        $(
+            impl Aggregatable for $t {
+                const ZERO: Self = 0;
+
+                #[inline]
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_aggregatable!(u8 u16 u32 u64 u128 i8 i16 i32 i64 i128);
+
+/// Running count, sum, min and max of parsed fields
+///
+/// Fields that fail to parse are counted apart and do not enter the totals. The sum is
+/// checked: once it overflows `T` it is no longer reported, while count, min and max
+/// keep being updated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecimalAggregator<T> {
+    fields: usize,
+    count: usize,
+    sum: T,
+    sum_overflowed: bool,
+    min: Option<T>,
+    max: Option<T>,
+    parse_errors: usize,
+    first_parse_error: Option<(usize, ParseIntErr)>,
+    // index i counts the values with i significant digits
+    digit_histogram: Option<Vec<usize>>,
+}
+
+impl<T: Aggregatable> Default for DecimalAggregator<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Aggregatable> DecimalAggregator<T> {
+    pub fn new() -> Self {
+        DecimalAggregator {
+            fields: 0,
+            count: 0,
+            sum: T::ZERO,
+            sum_overflowed: false,
+            min: None,
+            max: None,
+            parse_errors: 0,
+            first_parse_error: None,
+            digit_histogram: None,
+        }
+    }
+
+    /// also counts the values by their number of significant digits
+    pub fn with_digit_histogram() -> Self {
+        DecimalAggregator { digit_histogram: Some(vec![0; T::MAX_DIGITS + 1]), ..Self::new() }
+    }
+
+    pub fn push_field(&mut self, field: &[u8]) {
+        let index = self.fields;
+        self.fields += 1;
+        let val = match T::parse_decimal(field) {
+            Ok(val) => val,
+            Err(err) => {
+                self.parse_errors += 1;
+                if self.first_parse_error.is_none() {
+                    self.first_parse_error = Some((index, err));
+                }
+                return;
+            },
+        };
+
+        self.count += 1;
+        if !self.sum_overflowed {
+            match self.sum.checked_add(val) {
+                Some(sum) => self.sum = sum,
+                None => self.sum_overflowed = true,
+            }
+        }
+        self.min = Some(self.min.map_or(val, |min| min.min(val)));
+        self.max = Some(self.max.map_or(val, |max| max.max(val)));
+
+        if let Some(histogram) = self.digit_histogram.as_mut() {
+            let digits = match field {
+                [b'-', rest @ ..] => rest,
+                _ => field,
+            };
+            let leading_zeros = digits.iter().take_while(|&&b| b == b'0').count().min(digits.len() - 1);
+            histogram[digits.len() - leading_zeros] += 1;
+        }
+    }
+
+    /// Feeds the `delim`-separated fields of `buf`, a single trailing delimiter ends the last field
+    ///
+    /// A call only takes complete fields, i.e., `buf` is not joined with the previous one.
+    pub fn feed_delimited(&mut self, buf: &[u8], delim: u8) {
+        let buf = match buf {
+            [] => return,
+            [rest @ .., last] if *last == delim => rest,
+            _ => buf,
+        };
+        for field in buf.split(|&b| b == delim) {
+            self.push_field(field);
+        }
+    }
+
+    /// Feeds `column` of the fixed-width records of `buf`, a trailing partial record is ignored
+    ///
+    /// # Panics
+    /// Panics if `stride` is zero or the column does not fit in the record.
+    pub fn feed_fixed_width(&mut self, buf: &[u8], stride: usize, column: Column) {
+        assert!(stride > 0, "record stride must be positive");
+        assert!(
+            column.offset + column.width <= stride,
+            "column {:?} exceeds the record stride {}", column, stride,
+        );
+        for record in buf.chunks_exact(stride) {
+            self.push_field(&record[column.offset..column.offset + column.width]);
+        }
+    }
+
+    /// fields seen, parsed or not
+    #[inline]
+    pub fn fields(&self) -> usize {
+        self.fields
+    }
+
+    /// fields parsed
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// `None` once the sum overflowed `T`
+    #[inline]
+    pub fn sum(&self) -> Option<T> {
+        if self.sum_overflowed { None } else { Some(self.sum) }
+    }
+
+    #[inline]
+    pub fn is_sum_overflowed(&self) -> bool {
+        self.sum_overflowed
+    }
+
+    #[inline]
+    pub fn min(&self) -> Option<T> {
+        self.min
+    }
+
+    #[inline]
+    pub fn max(&self) -> Option<T> {
+        self.max
+    }
+
+    #[inline]
+    pub fn parse_errors(&self) -> usize {
+        self.parse_errors
+    }
+
+    /// field index and error of the first field that failed to parse
+    #[inline]
+    pub fn first_parse_error(&self) -> Option<(usize, ParseIntErr)> {
+        self.first_parse_error.clone()
+    }
+
+    /// index i holds the number of values with i significant digits (0 has one digit),
+    /// `None` unless created by [`DecimalAggregator::with_digit_histogram`]
+    #[inline]
+    pub fn digit_histogram(&self) -> Option<&[usize]> {
+        self.digit_histogram.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_totals() {
+        let mut agg = DecimalAggregator::<i32>::with_digit_histogram();
+        agg.feed_delimited(b"-5,0012,0,-0,7,,100000", b',');
+        assert_eq!(agg.fields(), 7);
+        assert_eq!(agg.count(), 6);
+        assert_eq!(agg.sum(), Some(100_014));
+        assert_eq!(agg.min(), Some(-5));
+        assert_eq!(agg.max(), Some(100_000));
+        assert_eq!(agg.parse_errors(), 1);
+        assert_eq!(agg.first_parse_error(), Some((5, ParseIntErr::Empty)));
+        assert_eq!(agg.digit_histogram().unwrap()[..7], [0, 4, 1, 0, 0, 0, 1]);
+        assert_eq!(agg.digit_histogram().unwrap().len(), 11);
+    }
+
+    #[test]
+    fn test_sum_overflow() {
+        let mut agg = DecimalAggregator::<u8>::new();
+        agg.feed_delimited(b"200\n100\n256\n3\n", b'\n');
+        assert_eq!(agg.count(), 3);
+        assert_eq!(agg.sum(), None);
+        assert!(agg.is_sum_overflowed());
+        assert_eq!(agg.min(), Some(3));
+        assert_eq!(agg.max(), Some(200));
+        // the accumulator overflow is not a parse error
+        assert_eq!(agg.parse_errors(), 1);
+        assert_eq!(agg.first_parse_error(), Some((2, ParseIntErr::Overflow)));
+    }
+
+    #[test]
+    fn test_fixed_width() {
+        let mut buf = Vec::new();
+        for i in 0..1_000u64 {
+            buf.extend_from_slice(format!("AAPL{:010}{:06}\n", i * 1_000, i).as_bytes());
+        }
+        let mut volume = DecimalAggregator::<u64>::new();
+        volume.feed_fixed_width(&buf, 21, Column::new(4, 10));
+        assert_eq!(volume.count(), 1_000);
+        assert_eq!(volume.sum(), Some(1_000 * 999 / 2 * 1_000));
+        assert_eq!(volume.min(), Some(0));
+        assert_eq!(volume.max(), Some(999_000));
+
+        let empty = DecimalAggregator::<u64>::new();
+        assert_eq!((empty.sum(), empty.min(), empty.max()), (Some(0), None, None));
+        assert_eq!(empty.digit_histogram(), None);
+    }
+}
@@ -0,0 +1,502 @@
+//! Packed BCD, two digits per byte with the first digit in the high nibble
+//!
+//! A trailing sign nibble is optional: `C` is positive, `D` negative and `F` unsigned,
+//! and the alternate nibbles `A` and `E` are read as positive and `B` as negative.
+//! Nibbles are validated a word at a time, and the bytes are folded with the same
+//! pairwise tree as [`eight_to_u64`](crate::kernels::eight_to_u64), starting from the
+//! two-digit value of each byte.
+//!
+//! ```rust
+//! use biscuit_converter::bcd::parse_packed_bcd;
+//! use biscuit_converter::error::ParseIntErr;
+//!
+//! assert_eq!(parse_packed_bcd::<u32>(&[0x12, 0x34, 0x56]), Ok(123456));
+//! assert_eq!(parse_packed_bcd::<i32>(&[0x01, 0x23, 0x4D]), Ok(-1234));
+//! assert_eq!(parse_packed_bcd::<u16>(&[0x00, 0x42, 0x0F]), Ok(420));
+//! assert_eq!(parse_packed_bcd::<u8>(&[0x02, 0x56]), Err(ParseIntErr::Overflow));
+//! assert_eq!(parse_packed_bcd::<u32>(&[0x1A, 0x2C]), Err(ParseIntErr::NonDecimal));
+//! ```
+//!
+//! [`write_packed_bcd`] is the inverse, into a fixed number of bytes:
+//!
+//! ```rust
+//! use biscuit_converter::bcd::{write_packed_bcd, BcdWriteErr, SignNibble};
+//!
+//! let mut out = [0u8; 4];
+//! write_packed_bcd(-1234i32, SignNibble::Signed, &mut out).unwrap();
+//! assert_eq!(out, [0x00, 0x01, 0x23, 0x4D]);
+//! write_packed_bcd(1234u16, SignNibble::None, &mut out).unwrap();
+//! assert_eq!(out, [0x00, 0x00, 0x12, 0x34]);
+//! assert_eq!(write_packed_bcd(123_456_789u32, SignNibble::Signed, &mut out), Err(BcdWriteErr::Width { required: 5 }));
+//! ```
+use crate::SignMagnitude;
+use crate::error::ParseIntErr;
+use std::fmt;
+
+const LOW_NIBBLES_U64: u64 = 0x0F0F_0F0F_0F0F_0F0F;
+const LOW_NIBBLES_U128: u128 = 0x0F0F_0F0F_0F0F_0F0F_0F0F_0F0F_0F0F_0F0F;
+const TEN_POW_16: u128 = 10_000_000_000_000_000;
+const TEN_POW_32: u128 = 100_000_000_000_000_000_000_000_000_000_000;
+// 48 digits, enough for u128::MAX
+const PACKED_BUFFER_LEN: usize = 24;
+// 39 digits of `u128::MAX` with a leading zero nibble
+const MAX_PACKED_LEN: usize = 20;
+
+pub const SIGN_POSITIVE: u8 = 0x0C;
+pub const SIGN_NEGATIVE: u8 = 0x0D;
+pub const SIGN_UNSIGNED: u8 = 0x0F;
+
+/// `Some(negative)` for a sign nibble, `None` for a digit
+#[inline]
+fn sign_of_nibble(nibble: u8) -> Option<bool> {
+    match nibble {
+        0x0B | SIGN_NEGATIVE => Some(true),
+        0x0A..=0x0F => Some(false),
+        _ => None,
+    }
+}
+
+/// `true` iff every nibble of the chunk is at most 9
+#[inline]
+#[must_use]
+pub fn check_packed_bcd_u64(chunk: u64) -> bool {
+    // bit 3 of (n & 7) + 6 is set iff n & 7 >= 2, so n >= 10 sets it together with bit 3 of n
+    let carry = (chunk & 0x7777_7777_7777_7777) + 0x6666_6666_6666_6666;
+    chunk & carry & 0x8888_8888_8888_8888 == 0
+}
+
+/// Sixteen-byte version of [`check_packed_bcd_u64`]
+#[inline]
+#[must_use]
+pub fn check_packed_bcd_u128(chunk: u128) -> bool {
+    let carry = (chunk & 0x7777_7777_7777_7777_7777_7777_7777_7777) + 0x6666_6666_6666_6666_6666_6666_6666_6666;
+    chunk & carry & 0x8888_8888_8888_8888_8888_8888_8888_8888 == 0
+}
+
+/// Eight packed BCD bytes loaded little-endian to their sixteen-digit value.
+/// The nibbles are not validated.
+#[inline]
+#[must_use]
+pub fn packed_eight_to_u64(mut chunk: u64) -> u64 {
+    // byte trick, 16 * high + low to 10 * high + low
+    chunk -= ((chunk >> 4) & LOW_NIBBLES_U64) * 6;
+
+    // 2-byte mask trick (works on 4 pairs of two digits)
+    let lower_digits = (chunk & 0xff00ff00ff00ff00) >> 8;
+    let upper_digits = (chunk & 0x00ff00ff00ff00ff) * 100;
+    chunk = lower_digits + upper_digits;
+
+    // 4-byte mask trick (works on 2 pairs of four digits)
+    let lower_digits = (chunk & 0xffff0000ffff0000) >> 16;
+    let upper_digits = (chunk & 0x0000ffff0000ffff) * 10000;
+    chunk = lower_digits + upper_digits;
+
+    // 8-byte mask trick (works on a pair of eight digits)
+    let lower_digits = (chunk & 0xffffffff00000000) >> 32;
+    let upper_digits = (chunk & 0x00000000ffffffff) * 100_000_000;
+    lower_digits + upper_digits
+}
+
+/// Sixteen packed BCD bytes loaded little-endian to their 32-digit value.
+/// The nibbles are not validated.
+#[inline]
+#[must_use]
+pub fn packed_sixteen_to_u128(mut chunk: u128) -> u128 {
+    chunk -= ((chunk >> 4) & LOW_NIBBLES_U128) * 6;
+    // 2-byte mask trick (works on 8 pairs of two digits)
+    let lower_digits = (chunk & 0xff00ff00ff00ff00ff00ff00ff00ff00) >> 8;
+    let upper_digits = (chunk & 0x00ff00ff00ff00ff00ff00ff00ff00ff) * 100;
+    chunk = lower_digits + upper_digits;
+    // 4-byte mask trick (works on 4 pairs of four digits)
+    let lower_digits = (chunk & 0xffff0000ffff0000ffff0000ffff0000) >> 16;
+    let upper_digits = (chunk & 0x0000ffff0000ffff0000ffff0000ffff) * 10000;
+    chunk = lower_digits + upper_digits;
+    // 8-byte mask trick (works on 2 pairs of eight digits)
+    let lower_digits = (chunk & 0xffffffff00000000ffffffff00000000) >> 32;
+    let upper_digits = (chunk & 0x00000000ffffffff00000000ffffffff) * 100_000_000;
+    chunk = lower_digits + upper_digits;
+    // 16-byte mask trick (works on a pair of sixteen digits)
+    let lower_digits = (chunk & 0xffffffffffffffff0000000000000000) >> 64;
+    let upper_digits = (chunk & 0x0000000000000000ffffffffffffffff) * 10_000_000_000_000_000;
+    lower_digits + upper_digits
+}
+
+// x < 10^8 to four packed BCD bytes, the first two digits in the lowest byte
+#[inline]
+fn eight_digits_to_packed(x: u32) -> u32 {
+    // 32-bit lanes [x / 10^4, x % 10^4]
+    let chunk = (x / 10_000) as u64 | (((x % 10_000) as u64) << 32);
+
+    // division by 100 of both lanes, 16-bit lanes of two digits
+    let upper_digits = ((chunk * 10_486) >> 20) & 0x0000_007f_0000_007f;
+    let lower_digits = chunk - upper_digits * 100;
+    let chunk = upper_digits | (lower_digits << 16);
+
+    // division by 10 of every lane, 16 * tens + ones
+    let tens = ((chunk * 103) >> 10) & 0x000f_000f_000f_000f;
+    let chunk = chunk + tens * 6;
+
+    // one byte per lane
+    let chunk = (chunk | (chunk >> 8)) & 0x0000_ffff_0000_ffff;
+    (chunk | (chunk >> 16)) as u32
+}
+
+/// Value below 10^16 to eight packed BCD bytes, little-endian as in [`packed_eight_to_u64`]
+#[inline]
+#[must_use]
+pub fn u64_to_packed_eight(val: u64) -> u64 {
+    debug_assert!(val < TEN_POW_16 as u64);
+    let upper = eight_digits_to_packed((val / 100_000_000) as u32) as u64;
+    let lower = eight_digits_to_packed((val % 100_000_000) as u32) as u64;
+    upper | (lower << 32)
+}
+
+// right-aligned, the most significant byte first
+#[inline]
+fn magnitude_to_packed(mut val: u128, buf: &mut [u8; PACKED_BUFFER_LEN]) {
+    for chunk in buf.rchunks_exact_mut(8) {
+        chunk.copy_from_slice(&u64_to_packed_eight((val % TEN_POW_16) as u64).to_le_bytes());
+        val /= TEN_POW_16;
+        if val == 0 {
+            break;
+        }
+    }
+}
+
+// 1..=8 bytes at the top of the word, zero bytes (leading "00" digits) below
+#[inline]
+fn load_packed_u64(u: &[u8]) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes[8 - u.len()..].copy_from_slice(u);
+    u64::from_le_bytes(bytes)
+}
+
+#[inline]
+fn load_packed_u128(u: &[u8]) -> u128 {
+    let mut bytes = [0u8; 16];
+    bytes[16 - u.len()..].copy_from_slice(u);
+    u128::from_le_bytes(bytes)
+}
+
+#[inline]
+fn checked_packed_u64(u: &[u8]) -> Result<u64, ParseIntErr> {
+    let chunk = load_packed_u64(u);
+    if check_packed_bcd_u64(chunk) { Ok(packed_eight_to_u64(chunk)) } else { Err(ParseIntErr::NonDecimal) }
+}
+
+// value of whole digit pairs, leading zero bytes already removed
+#[inline]
+fn packed_magnitude(u: &[u8]) -> Result<Option<u128>, ParseIntErr> {
+    Ok(match u.len() {
+        0 => Some(0),
+        1..=8 => Some(checked_packed_u64(u)? as u128),
+        9..=16 => {
+            let chunk = load_packed_u128(u);
+            if !check_packed_bcd_u128(chunk) {
+                return Err(ParseIntErr::NonDecimal);
+            }
+            Some(packed_sixteen_to_u128(chunk))
+        },
+        17..=MAX_PACKED_LEN => {
+            let (head, tail) = u.split_at(u.len() - 16);
+            let head = checked_packed_u64(head)? as u128;
+            let tail = load_packed_u128(tail);
+            if !check_packed_bcd_u128(tail) {
+                return Err(ParseIntErr::NonDecimal);
+            }
+            head.checked_mul(TEN_POW_32).and_then(|val| val.checked_add(packed_sixteen_to_u128(tail)))
+        },
+        _ => None,
+    })
+}
+
+/// Decodes packed BCD with an optional trailing sign nibble (`A` to `F`)
+///
+/// `B` and `D` are negative, the other sign nibbles positive. A negative sign is
+/// `NonDecimal` for unsigned types, as is `-` in decimal text.
+pub fn parse_packed_bcd<T: SignMagnitude>(u: &[u8]) -> Result<T, ParseIntErr> {
+    let Some((&last, init)) = u.split_last() else {
+        return Err(ParseIntErr::Empty);
+    };
+    let (neg, pairs, last_digit) = match sign_of_nibble(last & 0x0F) {
+        Some(neg) => (neg, init, Some(last >> 4)),
+        None => (false, u, None),
+    };
+
+    let start = pairs.iter().position(|&b| b != 0).unwrap_or(pairs.len());
+    let mut magnitude = packed_magnitude(&pairs[start..])?;
+    if let Some(digit) = last_digit {
+        if digit > 9 {
+            return Err(ParseIntErr::NonDecimal);
+        }
+        magnitude = magnitude.and_then(|val| val.checked_mul(10)).and_then(|val| val.checked_add(digit as u128));
+    }
+    T::from_sign_magnitude(neg, magnitude)
+}
+
+/// Sign nibble written after the digits by [`write_packed_bcd`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignNibble {
+    /// every nibble is a digit, negative values are rejected
+    None,
+    /// `C` for zero and positive values, `D` for negative ones
+    Signed,
+    /// `F` for zero and positive values, `D` for negative ones
+    Unsigned,
+    /// `positive` in `A`, `C`, `E` or `F` and `negative` in `B` or `D`, the nibbles
+    /// [`parse_packed_bcd`] reads back with the same sign
+    Custom { positive: u8, negative: u8 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BcdWriteErr {
+    /// the value needs `required` bytes
+    Width { required: usize },
+    /// a negative value without a sign nibble
+    Negative,
+    /// a `SignNibble::Custom` nibble that is not read back with its sign
+    SignNibble { nibble: u8 },
+}
+
+impl fmt::Display for BcdWriteErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BcdWriteErr::Width { required } => write!(f, "packed BCD needs {} bytes", required),
+            BcdWriteErr::Negative => write!(f, "negative value without a sign nibble"),
+            BcdWriteErr::SignNibble { nibble } => write!(f, "sign nibble {:#X} is not read back with its sign", nibble),
+        }
+    }
+}
+
+impl std::error::Error for BcdWriteErr {}
+
+/// Encodes `val` as packed BCD filling all of `out`, zero digits on the left
+///
+/// Nothing is written on error.
+pub fn write_packed_bcd<T: SignMagnitude>(val: T, sign: SignNibble, out: &mut [u8]) -> Result<(), BcdWriteErr> {
+    let (neg, magnitude) = val.to_sign_magnitude();
+    let sign = match sign {
+        SignNibble::None if neg => return Err(BcdWriteErr::Negative),
+        SignNibble::None => None,
+        SignNibble::Signed => Some(if neg { SIGN_NEGATIVE } else { SIGN_POSITIVE }),
+        SignNibble::Unsigned => Some(if neg { SIGN_NEGATIVE } else { SIGN_UNSIGNED }),
+        SignNibble::Custom { positive, negative } => {
+            if sign_of_nibble(positive) != Some(false) {
+                return Err(BcdWriteErr::SignNibble { nibble: positive });
+            }
+            if sign_of_nibble(negative) != Some(true) {
+                return Err(BcdWriteErr::SignNibble { nibble: negative });
+            }
+            Some(if neg { negative } else { positive })
+        },
+    };
+    let digits = magnitude.checked_ilog10().map_or(1, |log| log as usize + 1);
+    let required = (digits + sign.is_some() as usize).div_ceil(2);
+    if required > out.len() {
+        return Err(BcdWriteErr::Width { required });
+    }
+
+    let mut packed = [0u8; PACKED_BUFFER_LEN];
+    magnitude_to_packed(magnitude, &mut packed);
+    out.fill(0);
+    match sign {
+        None => {
+            let len = out.len().min(PACKED_BUFFER_LEN);
+            let start = out.len() - len;
+            out[start..].copy_from_slice(&packed[PACKED_BUFFER_LEN - len..]);
+        },
+        // the digits move up one nibble to make room for the sign
+        Some(sign) => {
+            let mut low_nibble = sign;
+            for (byte, &pair) in out.iter_mut().rev().zip(packed.iter().rev()) {
+                *byte = (pair << 4) | low_nibble;
+                low_nibble = pair >> 4;
+            }
+        },
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // reference encoder, digits of the decimal text two per byte
+    fn encode_digits(digits: &str, sign: Option<u8>) -> Vec<u8> {
+        let mut nibbles: Vec<u8> = digits.bytes().map(|b| b - b'0').collect();
+        if let Some(sign) = sign {
+            nibbles.push(sign);
+        }
+        if nibbles.len() % 2 == 1 {
+            nibbles.insert(0, 0);
+        }
+        nibbles.chunks(2).map(|pair| (pair[0] << 4) | pair[1]).collect()
+    }
+
+    fn encode(val: i128, sign: Option<u8>) -> Vec<u8> {
+        encode_digits(&val.unsigned_abs().to_string(), sign)
+    }
+
+    #[test]
+    fn test_kernels() {
+        assert_eq!(packed_eight_to_u64(u64::from_le_bytes([0x12, 0x34, 0x56, 0x78, 0x90, 0x12, 0x34, 0x56])), 1234567890123456);
+        assert_eq!(packed_eight_to_u64(u64::from_le_bytes([0x99; 8])), 9999999999999999);
+        assert_eq!(packed_sixteen_to_u128(u128::from_le_bytes([0x99; 16])), 10u128.pow(32) - 1);
+        for nibble in 0..16u64 {
+            for shift in (0..64).step_by(4) {
+                assert_eq!(check_packed_bcd_u64(nibble << shift), nibble <= 9);
+                assert_eq!(check_packed_bcd_u64(0x9999_9999_9999_9999 & !(0xF << shift) | nibble << shift), nibble <= 9);
+                assert_eq!(check_packed_bcd_u128((nibble as u128) << (2 * shift)), nibble <= 9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_against_encoder() {
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        for _ in 0..10_000 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let x = ((seed as i128) << 64 | seed as i128) >> (seed % 127);
+            assert_eq!(parse_packed_bcd::<i128>(&encode(x, Some(if x < 0 { 0x0D } else { 0x0C }))), Ok(x));
+            assert_eq!(parse_packed_bcd::<u128>(&encode(x, Some(0x0F))), Ok(x.unsigned_abs()));
+            assert_eq!(parse_packed_bcd::<u128>(&encode(x, None)), Ok(x.unsigned_abs()));
+            assert_eq!(parse_packed_bcd::<u64>(&encode(x, None)), u64::try_from(x.unsigned_abs()).map_err(|_| ParseIntErr::Overflow));
+            assert_eq!(parse_packed_bcd::<i32>(&encode(x, Some(0x0D))), i32::try_from(-x.abs()).map_err(|_| ParseIntErr::NegOverflow));
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        assert_eq!(parse_packed_bcd::<u8>(&[0x00; 30]), Ok(0));
+        assert_eq!(parse_packed_bcd::<u8>(&[0x00, 0x00, 0x00, 0x25, 0x5C]), Ok(255));
+        assert_eq!(parse_packed_bcd::<u8>(&[0x25, 0x5F]), Ok(255));
+        assert_eq!(parse_packed_bcd::<u8>(&[0x25, 0x6C]), Err(ParseIntErr::Overflow));
+        assert_eq!(parse_packed_bcd::<i8>(&[0x12, 0x8D]), Ok(-128));
+        assert_eq!(parse_packed_bcd::<i8>(&[0x12, 0x9D]), Err(ParseIntErr::NegOverflow));
+        assert_eq!(parse_packed_bcd::<i8>(&[0x12, 0x8C]), Err(ParseIntErr::Overflow));
+        assert_eq!(parse_packed_bcd::<i128>(&[0x0D]), Ok(0));
+        assert_eq!(parse_packed_bcd::<u128>(&[0x01; 21]), Err(ParseIntErr::Overflow));
+
+        // u128::MAX, i128::MIN
+        let mut u = encode_digits("340282366920938463463374607431768211455", None);
+        assert_eq!(parse_packed_bcd::<u128>(&u), Ok(u128::MAX));
+        *u.last_mut().unwrap() += 1;
+        assert_eq!(parse_packed_bcd::<u128>(&u), Err(ParseIntErr::Overflow));
+        assert_eq!(parse_packed_bcd::<i128>(&encode(i128::MIN, Some(0x0D))), Ok(i128::MIN));
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert_eq!(parse_packed_bcd::<u32>(&[]), Err(ParseIntErr::Empty));
+        assert_eq!(parse_packed_bcd::<u32>(&[0x1B]), Err(ParseIntErr::NonDecimal));
+        assert_eq!(parse_packed_bcd::<u32>(&[0xAC]), Err(ParseIntErr::NonDecimal));
+        assert_eq!(parse_packed_bcd::<u32>(&[0x12, 0x3C, 0x4C]), Err(ParseIntErr::NonDecimal));
+        assert_eq!(parse_packed_bcd::<u32>(&[0x12, 0x34, 0x5D]), Err(ParseIntErr::NonDecimal));
+        // a trailing `E` is a positive sign
+        assert_eq!(parse_packed_bcd::<u32>(&[0x11, 0x1E]), Ok(111));
+        for i in 0..19 {
+            let mut u = vec![0x11; 20];
+            u[i] = 0x1E;
+            assert_eq!(parse_packed_bcd::<u128>(&u), Err(ParseIntErr::NonDecimal), "Failed at byte {}", i);
+            u[i] = 0xE1;
+            assert_eq!(parse_packed_bcd::<u128>(&u), Err(ParseIntErr::NonDecimal), "Failed at byte {}", i);
+        }
+    }
+
+    #[test]
+    fn test_encode_kernel() {
+        for x in [0u64, 1, 9, 10, 99, 100, 1234, 9999, 10_000, 12_345_678, 99_999_999, 100_000_000, 1_234_567_890_123_456, TEN_POW_16 as u64 - 1] {
+            let expected = encode_digits(&format!("{:016}", x), None);
+            assert_eq!(u64_to_packed_eight(x).to_le_bytes(), expected[..], "Failed for {}", x);
+            assert_eq!(packed_eight_to_u64(u64_to_packed_eight(x)), x);
+        }
+        for x in (0..100_000_000u32).step_by(7_919) {
+            assert_eq!(eight_digits_to_packed(x).to_le_bytes(), encode_digits(&format!("{:08}", x), None)[..]);
+        }
+    }
+
+    #[test]
+    fn test_write_round_trip() {
+        let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+        let mut out = [0u8; 21];
+        for _ in 0..10_000 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let x = ((seed as i128) << 64 | seed as i128) >> (seed % 127);
+            for sign in [SignNibble::Signed, SignNibble::Unsigned] {
+                write_packed_bcd(x, sign, &mut out).unwrap();
+                assert_eq!(parse_packed_bcd::<i128>(&out), Ok(x));
+                write_packed_bcd(x as i32, sign, &mut out[15..]).unwrap();
+                assert_eq!(parse_packed_bcd::<i32>(&out[15..]), Ok(x as i32));
+            }
+            write_packed_bcd(x as u128, SignNibble::None, &mut out[1..]).unwrap();
+            assert_eq!(out[1..], encode_digits(&format!("{:040}", x as u128), None)[..]);
+            write_packed_bcd(x as u8, SignNibble::None, &mut out[19..]).unwrap();
+            assert_eq!(parse_packed_bcd::<u8>(&out[19..]), Ok(x as u8));
+            write_packed_bcd(x as i64, SignNibble::Signed, &mut out[11..]).unwrap();
+            let sign = if (x as i64) < 0 { 0x0D } else { 0x0C };
+            assert_eq!(out[11..], encode_digits(&format!("{:019}", (x as i64).unsigned_abs()), Some(sign))[..]);
+        }
+    }
+
+    #[test]
+    fn test_custom_sign_round_trip() {
+        let mut out = [0u8; 17];
+        let mut accepted = Vec::new();
+        for positive in 0..=0xFF {
+            for negative in 0..=0xFF {
+                let sign = SignNibble::Custom { positive, negative };
+                if write_packed_bcd(0i8, sign, &mut out).is_err() {
+                    continue;
+                }
+                accepted.push((positive, negative));
+                for x in [0, 1, -1, 7, -7, 1234, -1234, i64::MIN + 1, i64::MIN, i64::MAX] {
+                    write_packed_bcd(x, sign, &mut out).unwrap();
+                    assert_eq!(parse_packed_bcd::<i64>(&out), Ok(x), "Failed for {} with {:?}", x, sign);
+                    if x >= 0 {
+                        write_packed_bcd(x as u64, sign, &mut out).unwrap();
+                        assert_eq!(parse_packed_bcd::<u64>(&out), Ok(x as u64), "Failed for {} with {:?}", x, sign);
+                    }
+                }
+            }
+        }
+        assert_eq!(accepted, [(0xA, 0xB), (0xA, 0xD), (0xC, 0xB), (0xC, 0xD), (0xE, 0xB), (0xE, 0xD), (0xF, 0xB), (0xF, 0xD)]);
+    }
+
+    #[test]
+    fn test_write_width() {
+        let mut out = [0xFFu8; 40];
+        write_packed_bcd(i128::MIN, SignNibble::Signed, &mut out).unwrap();
+        assert_eq!(out[..20], [0; 20]);
+        assert_eq!(parse_packed_bcd::<i128>(&out), Ok(i128::MIN));
+        write_packed_bcd(u128::MAX, SignNibble::None, &mut out).unwrap();
+        assert_eq!(parse_packed_bcd::<u128>(&out), Ok(u128::MAX));
+        assert_eq!(write_packed_bcd(u128::MAX, SignNibble::None, &mut out[..19]), Err(BcdWriteErr::Width { required: 20 }));
+        // 39 digits and the sign nibble fill 20 bytes
+        assert_eq!(write_packed_bcd(u128::MAX, SignNibble::Unsigned, &mut out[..20]), Ok(()));
+        assert_eq!(parse_packed_bcd::<u128>(&out[..20]), Ok(u128::MAX));
+        assert_eq!(write_packed_bcd(u128::MAX, SignNibble::Unsigned, &mut out[..19]), Err(BcdWriteErr::Width { required: 20 }));
+
+        let mut out = [0xFFu8; 2];
+        assert_eq!(write_packed_bcd(999u16, SignNibble::Signed, &mut out), Ok(()));
+        assert_eq!(out, [0x99, 0x9C]);
+        assert_eq!(write_packed_bcd(1000u16, SignNibble::Signed, &mut out), Err(BcdWriteErr::Width { required: 3 }));
+        assert_eq!(out, [0x99, 0x9C]);
+        assert_eq!(write_packed_bcd(9999u16, SignNibble::None, &mut out), Ok(()));
+        assert_eq!(out, [0x99, 0x99]);
+        assert_eq!(write_packed_bcd(-1i8, SignNibble::None, &mut out), Err(BcdWriteErr::Negative));
+        assert_eq!(write_packed_bcd(0i8, SignNibble::Unsigned, &mut out), Ok(()));
+        assert_eq!(out, [0x00, 0x0F]);
+        assert_eq!(write_packed_bcd(-7i8, SignNibble::Custom { positive: 0xA, negative: 0xB }, &mut out), Ok(()));
+        assert_eq!(out, [0x00, 0x7B]);
+        assert_eq!(write_packed_bcd(7i8, SignNibble::Custom { positive: 0x3, negative: 0xD }, &mut out), Err(BcdWriteErr::SignNibble { nibble: 0x3 }));
+        assert_eq!(write_packed_bcd(7i8, SignNibble::Custom { positive: 0xC, negative: 0x1D }, &mut out), Err(BcdWriteErr::SignNibble { nibble: 0x1D }));
+        assert_eq!(write_packed_bcd(7i8, SignNibble::Custom { positive: 0xB, negative: 0xD }, &mut out), Err(BcdWriteErr::SignNibble { nibble: 0xB }));
+        assert_eq!(write_packed_bcd(7i8, SignNibble::Custom { positive: 0xC, negative: 0xE }, &mut out), Err(BcdWriteErr::SignNibble { nibble: 0xE }));
+        assert_eq!(out, [0x00, 0x7B]);
+        assert_eq!(write_packed_bcd(0u8, SignNibble::Signed, &mut []), Err(BcdWriteErr::Width { required: 1 }));
+    }
+}
@@ -0,0 +1,317 @@
+//! Arbitrary-precision decimal parsing
+//!
+//! The input is cut into 16-digit blocks, each converted by the SWAR kernel, and the
+//! blocks are combined by divide and conquer: the lower half of the blocks is joined to
+//! the upper half with one multiplication by `10^(16 * 2^k)`. With Karatsuba
+//! multiplication for large operands, long inputs are not parsed in quadratic time.
+//!
+//! ```rust
+//! use biscuit_converter::big::parse_decimal_big;
+//!
+//! let digits = "123456789".repeat(40);
+//! let big = parse_decimal_big(digits.as_bytes()).unwrap();
+//! assert_eq!(big.to_string(), digits);
+//! assert_eq!(parse_decimal_big(b"18446744073709551616").unwrap().limbs(), &[0, 1]);
+//! ```
+use crate::error::ParseIntErr;
+use crate::little_endian_decimal::checked_conversion_u128;
+use std::fmt;
+
+const BLOCK_DIGITS: usize = 16;
+const TEN_POW_16: u64 = 10_000_000_000_000_000;
+const TEN_POW_19: u64 = 10_000_000_000_000_000_000;
+// below this many blocks, the blocks are accumulated one by one
+const LINEAR_BLOCKS: usize = 32;
+// below this many limbs in the shorter operand, schoolbook multiplication is used
+const KARATSUBA_LIMBS: usize = 32;
+
+/// Unsigned integer of any size as little-endian `u64` limbs
+///
+/// The limbs are normalized, i.e., the most significant limb is never zero and zero
+/// has no limbs at all.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigDigits {
+    limbs: Vec<u64>,
+}
+
+impl BigDigits {
+    /// `limbs[0]` is the least significant limb
+    pub fn from_limbs(mut limbs: Vec<u64>) -> Self {
+        trim(&mut limbs);
+        BigDigits { limbs }
+    }
+
+    #[inline]
+    pub fn limbs(&self) -> &[u64] {
+        &self.limbs
+    }
+
+    #[inline]
+    pub fn into_limbs(self) -> Vec<u64> {
+        self.limbs
+    }
+
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+}
+
+impl fmt::Display for BigDigits {
+    /// quadratic in the number of limbs, meant for logs and tests rather than bulk output
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.limbs.is_empty() {
+            return f.pad_integral(true, "", "0");
+        }
+        // 19-digit groups, least significant first
+        let mut groups = Vec::with_capacity(self.limbs.len() * 64 / 63 + 1);
+        let mut rest = self.limbs.clone();
+        while !rest.is_empty() {
+            let mut rem = 0u128;
+            for limb in rest.iter_mut().rev() {
+                let t = (rem << 64) | *limb as u128;
+                *limb = (t / TEN_POW_19 as u128) as u64;
+                rem = t % TEN_POW_19 as u128;
+            }
+            trim(&mut rest);
+            groups.push(rem as u64);
+        }
+
+        let mut buf = groups.last().unwrap().to_string();
+        for group in groups.iter().rev().skip(1) {
+            buf.push_str(&format!("{:019}", group));
+        }
+        f.pad_integral(true, "", &buf)
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl From<BigDigits> for num_bigint::BigUint {
+    fn from(val: BigDigits) -> Self {
+        let digits = val.limbs.iter()
+            .flat_map(|&limb| [limb as u32, (limb >> 32) as u32])
+            .collect();
+        num_bigint::BigUint::new(digits)
+    }
+}
+
+/// Parses a decimal of any length
+///
+/// Leading zeros are allowed, a sign is not.
+pub fn parse_decimal_big(u: &[u8]) -> Result<BigDigits, ParseIntErr> {
+    if u.is_empty() {
+        return Err(ParseIntErr::Empty);
+    }
+    let start = u.iter().position(|&x| x != b'0').unwrap_or(u.len() - 1);
+    let u = &u[start..];
+
+    // most significant block first, the first block takes the remainder
+    let head = (u.len() - 1) % BLOCK_DIGITS + 1;
+    let mut blocks = Vec::with_capacity(u.len().div_ceil(BLOCK_DIGITS));
+    blocks.push(checked_conversion_u128(&u[..head])? as u64);
+    for chunk in u[head..].chunks_exact(BLOCK_DIGITS) {
+        blocks.push(checked_conversion_u128(chunk)? as u64);
+    }
+
+    // powers[k] = 10^(16 * 2^k)
+    let mut powers = vec![vec![TEN_POW_16]];
+    while (1 << powers.len()) < blocks.len() {
+        let last = powers.last().unwrap();
+        powers.push(mul(last, last));
+    }
+
+    Ok(BigDigits::from_limbs(combine(&blocks, &powers)))
+}
+
+// value of the blocks, the upper part is at most as long as the lower part
+fn combine(blocks: &[u64], powers: &[Vec<u64>]) -> Vec<u64> {
+    if blocks.len() <= LINEAR_BLOCKS {
+        let mut acc = Vec::with_capacity(blocks.len());
+        for &block in blocks {
+            mul_small_add(&mut acc, TEN_POW_16, block);
+        }
+        return acc;
+    }
+    let k = (blocks.len() - 1).ilog2() as usize;
+    let (upper, lower) = blocks.split_at(blocks.len() - (1 << k));
+    let mut res = mul(&combine(upper, powers), &powers[k]);
+    add_shifted(&mut res, &combine(lower, powers), 0);
+    res
+}
+
+#[inline]
+fn trim(x: &mut Vec<u64>) {
+    while x.last() == Some(&0) {
+        x.pop();
+    }
+}
+
+// x = x * mul + add
+fn mul_small_add(x: &mut Vec<u64>, mul: u64, add: u64) {
+    let mut carry = add as u128;
+    for limb in x.iter_mut() {
+        let t = *limb as u128 * mul as u128 + carry;
+        *limb = t as u64;
+        carry = t >> 64;
+    }
+    if carry != 0 {
+        x.push(carry as u64);
+    }
+}
+
+// x += y << (64 * shift)
+fn add_shifted(x: &mut Vec<u64>, y: &[u64], shift: usize) {
+    if x.len() < shift + y.len() {
+        x.resize(shift + y.len(), 0);
+    }
+    let mut carry = false;
+    for (limb, &other) in x[shift..].iter_mut().zip(y) {
+        let (sum, c1) = limb.overflowing_add(other);
+        let (sum, c2) = sum.overflowing_add(carry as u64);
+        *limb = sum;
+        carry = c1 | c2;
+    }
+    let mut i = shift + y.len();
+    while carry {
+        if i == x.len() {
+            x.push(1);
+            break;
+        }
+        let (sum, c) = x[i].overflowing_add(1);
+        x[i] = sum;
+        carry = c;
+        i += 1;
+    }
+}
+
+// x -= y, the caller guarantees x >= y
+fn sub_assign(x: &mut Vec<u64>, y: &[u64]) {
+    let mut borrow = false;
+    for (limb, &other) in x.iter_mut().zip(y) {
+        let (diff, b1) = limb.overflowing_sub(other);
+        let (diff, b2) = diff.overflowing_sub(borrow as u64);
+        *limb = diff;
+        borrow = b1 | b2;
+    }
+    let mut i = y.len();
+    while borrow {
+        let (diff, b) = x[i].overflowing_sub(1);
+        x[i] = diff;
+        borrow = b;
+        i += 1;
+    }
+    trim(x);
+}
+
+fn schoolbook(x: &[u64], y: &[u64]) -> Vec<u64> {
+    let mut res = vec![0u64; x.len() + y.len()];
+    for (i, &a) in x.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, &b) in y.iter().enumerate() {
+            let t = a as u128 * b as u128 + res[i + j] as u128 + carry;
+            res[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        res[i + y.len()] = carry as u64;
+    }
+    trim(&mut res);
+    res
+}
+
+fn mul(x: &[u64], y: &[u64]) -> Vec<u64> {
+    let (long, short) = if x.len() >= y.len() { (x, y) } else { (y, x) };
+    if short.is_empty() {
+        return Vec::new();
+    }
+    if short.len() < KARATSUBA_LIMBS {
+        return schoolbook(long, short);
+    }
+
+    let half = long.len() / 2;
+    let (long_low, long_high) = long.split_at(half);
+    if short.len() <= half {
+        // only the longer operand is split
+        let mut res = mul(long_low, short);
+        add_shifted(&mut res, &mul(long_high, short), half);
+        trim(&mut res);
+        return res;
+    }
+
+    let (short_low, short_high) = short.split_at(half);
+    let low = mul(long_low, short_low);
+    let high = mul(long_high, short_high);
+    let mut long_sum = long_low.to_vec();
+    add_shifted(&mut long_sum, long_high, 0);
+    let mut short_sum = short_low.to_vec();
+    add_shifted(&mut short_sum, short_high, 0);
+    trim(&mut long_sum);
+    trim(&mut short_sum);
+    let mut mid = mul(&long_sum, &short_sum);
+    sub_assign(&mut mid, &low);
+    sub_assign(&mut mid, &high);
+
+    let mut res = low;
+    add_shifted(&mut res, &mid, half);
+    add_shifted(&mut res, &high, 2 * half);
+    trim(&mut res);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pseudo_random_limbs(len: usize, mut seed: u64) -> Vec<u64> {
+        (0..len).map(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        }).collect()
+    }
+
+    #[test]
+    fn test_karatsuba_against_schoolbook() {
+        for (x_len, y_len) in [(32, 32), (33, 40), (100, 37), (257, 256), (64, 500)] {
+            let x = pseudo_random_limbs(x_len, 0x9e37_79b9_7f4a_7c15);
+            let y = pseudo_random_limbs(y_len, 0xdead_beef_cafe_f00d);
+            assert_eq!(mul(&x, &y), schoolbook(&x, &y), "Failed for {} x {}", x_len, y_len);
+        }
+    }
+
+    #[test]
+    fn test_small_values() {
+        assert_eq!(parse_decimal_big(b"0"), Ok(BigDigits::default()));
+        assert_eq!(parse_decimal_big(b"0000"), Ok(BigDigits::default()));
+        assert_eq!(parse_decimal_big(b"18446744073709551615").unwrap().limbs(), &[u64::MAX]);
+        assert_eq!(parse_decimal_big(b"340282366920938463463374607431768211456").unwrap().limbs(), &[0, 0, 1]);
+        assert_eq!(parse_decimal_big(b""), Err(ParseIntErr::Empty));
+        assert_eq!(parse_decimal_big(b"-1"), Err(ParseIntErr::NonDecimal));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut digits = String::new();
+        for i in 0..5_000u64 {
+            digits.push(char::from(b'0' + ((i * 7 + i / 13) % 10) as u8));
+            let s = digits.trim_start_matches('0');
+            if s.is_empty() || i % 97 != 0 && i < 4_990 {
+                continue;
+            }
+            let big = parse_decimal_big(digits.as_bytes()).unwrap();
+            assert_eq!(big.to_string(), s, "Failed for {} digits", i + 1);
+        }
+
+        let mut digits = "7".repeat(4_000);
+        digits.replace_range(1_234..1_235, "x");
+        assert_eq!(parse_decimal_big(digits.as_bytes()), Err(ParseIntErr::NonDecimal));
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[test]
+    fn test_num_bigint() {
+        let digits = "31415926535897932384626433832795028841971".repeat(50);
+        let big = num_bigint::BigUint::from(parse_decimal_big(digits.as_bytes()).unwrap());
+        assert_eq!(big, digits.parse::<num_bigint::BigUint>().unwrap());
+    }
+}
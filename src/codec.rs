@@ -0,0 +1,240 @@
+//! `tokio_util` codec for newline-delimited integers (feature `tokio-util`)
+//!
+//! Frames are parsed in place from the read buffer, an optional `\r` before the `\n` is
+//! ignored and a last frame without `\n` is parsed at the end of the stream. As with
+//! `tokio_util::codec::LinesCodec`, a line longer than the maximum frame length is
+//! skipped up to its `\n` and reported once.
+//!
+//! ```rust
+//! use biscuit_converter::codec::DecimalLineCodec;
+//! use bytes::BytesMut;
+//! use tokio_util::codec::{Decoder, Encoder};
+//!
+//! let mut codec = DecimalLineCodec::<u64>::new();
+//! let mut buf = BytesMut::new();
+//! codec.encode(42, &mut buf).unwrap();
+//! codec.encode(7, &mut buf).unwrap();
+//! assert_eq!(&buf[..], b"42\n7\n");
+//!
+//! assert_eq!(codec.decode(&mut buf).unwrap(), Some(42));
+//! assert_eq!(codec.decode(&mut buf).unwrap(), Some(7));
+//! assert_eq!(codec.decode(&mut buf).unwrap(), None);
+//! ```
+use crate::Biscuit;
+use crate::error::ParseIntErr;
+use crate::format::{DecimalBuffer, FormatDecimal};
+use bytes::{Buf, BufMut, BytesMut};
+use std::fmt;
+use std::io;
+use std::marker::PhantomData;
+use tokio_util::codec::{Decoder, Encoder};
+
+#[derive(Debug)]
+pub enum DecimalCodecErr {
+    /// the line is longer than the maximum frame length
+    MaxLengthExceeded,
+    Parse(ParseIntErr),
+    Io(io::Error),
+}
+
+impl fmt::Display for DecimalCodecErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecimalCodecErr::MaxLengthExceeded => write!(f, "max frame length exceeded"),
+            DecimalCodecErr::Parse(err) => write!(f, "{}", err),
+            DecimalCodecErr::Io(err) => write!(f, "io error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for DecimalCodecErr {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DecimalCodecErr::MaxLengthExceeded => None,
+            DecimalCodecErr::Parse(err) => Some(err),
+            DecimalCodecErr::Io(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for DecimalCodecErr {
+    fn from(err: io::Error) -> Self {
+        DecimalCodecErr::Io(err)
+    }
+}
+
+impl From<ParseIntErr> for DecimalCodecErr {
+    fn from(err: ParseIntErr) -> Self {
+        DecimalCodecErr::Parse(err)
+    }
+}
+
+/// Decoder and encoder of one integer per line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecimalLineCodec<T> {
+    max_length: usize,
+    // bytes already searched for '\n'
+    next_index: usize,
+    discarding: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Default for DecimalLineCodec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> DecimalLineCodec<T> {
+    /// without a maximum frame length
+    pub fn new() -> Self {
+        Self::new_with_max_length(usize::MAX)
+    }
+
+    /// `max_length` does not count the line terminator
+    pub fn new_with_max_length(max_length: usize) -> Self {
+        DecimalLineCodec { max_length, next_index: 0, discarding: false, _marker: PhantomData }
+    }
+
+    #[inline]
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+}
+
+#[inline]
+fn without_cr(line: &[u8]) -> &[u8] {
+    match line {
+        [rest @ .., b'\r'] => rest,
+        _ => line,
+    }
+}
+
+impl<T: Biscuit> Decoder for DecimalLineCodec<T> {
+    type Item = T;
+    type Error = DecimalCodecErr;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<T>, DecimalCodecErr> {
+        loop {
+            // one byte more than the limit for the '\n', and another for a '\r'
+            let read_to = buf.len().min(self.max_length.saturating_add(2));
+            let newline = buf[self.next_index..read_to].iter().position(|&b| b == b'\n');
+
+            match (self.discarding, newline) {
+                (true, Some(offset)) => {
+                    buf.advance(self.next_index + offset + 1);
+                    self.discarding = false;
+                    self.next_index = 0;
+                },
+                (true, None) => {
+                    buf.advance(read_to);
+                    self.next_index = 0;
+                    if buf.is_empty() {
+                        return Ok(None);
+                    }
+                },
+                (false, Some(offset)) => {
+                    let end = self.next_index + offset;
+                    self.next_index = 0;
+                    let line = without_cr(&buf[..end]);
+                    if line.len() > self.max_length {
+                        buf.advance(end + 1);
+                        return Err(DecimalCodecErr::MaxLengthExceeded);
+                    }
+                    let res = T::parse_decimal(line);
+                    buf.advance(end + 1);
+                    return res.map(Some).map_err(DecimalCodecErr::Parse);
+                },
+                // only a trailing '\r' can still be cut from a line of max_length + 1 bytes
+                (false, None) if without_cr(&buf[..read_to]).len() > self.max_length => {
+                    self.discarding = true;
+                    return Err(DecimalCodecErr::MaxLengthExceeded);
+                },
+                (false, None) => {
+                    self.next_index = read_to;
+                    return Ok(None);
+                },
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<T>, DecimalCodecErr> {
+        if let Some(val) = self.decode(buf)? {
+            return Ok(Some(val));
+        }
+        if buf.is_empty() || self.discarding {
+            buf.clear();
+            self.discarding = false;
+            self.next_index = 0;
+            return Ok(None);
+        }
+        let line = without_cr(&buf[..]);
+        let res = if line.len() > self.max_length {
+            Err(DecimalCodecErr::MaxLengthExceeded)
+        } else {
+            T::parse_decimal(line).map(Some).map_err(DecimalCodecErr::Parse)
+        };
+        buf.clear();
+        self.next_index = 0;
+        res
+    }
+}
+
+impl<T: FormatDecimal> Encoder<T> for DecimalLineCodec<T> {
+    type Error = DecimalCodecErr;
+
+    fn encode(&mut self, val: T, buf: &mut BytesMut) -> Result<(), DecimalCodecErr> {
+        let mut digits = DecimalBuffer::new();
+        let digits = digits.format(val);
+        buf.reserve(digits.len() + 1);
+        buf.put_slice(digits);
+        buf.put_u8(b'\n');
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_frames() {
+        let mut codec = DecimalLineCodec::<i32>::new();
+        let mut buf = BytesMut::new();
+        let mut vals = Vec::new();
+        for chunk in [&b"12"[..], b"3", b"4\r", b"\n-5", b"6\n"] {
+            buf.extend_from_slice(chunk);
+            vals.extend(codec.decode(&mut buf).unwrap());
+        }
+        assert_eq!(vals, vec![1234, -56]);
+        assert!(buf.is_empty());
+
+        buf.extend_from_slice(b"\n");
+        assert!(matches!(codec.decode(&mut buf), Err(DecimalCodecErr::Parse(ParseIntErr::Empty))));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_max_length() {
+        let mut codec = DecimalLineCodec::<u64>::new_with_max_length(4);
+        let mut buf = BytesMut::from(&b"1234\n12345"[..]);
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(1234));
+        assert!(matches!(codec.decode(&mut buf), Err(DecimalCodecErr::MaxLengthExceeded)));
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        buf.extend_from_slice(b"678\n9");
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        assert_eq!(&buf[..], b"9");
+        buf.extend_from_slice(b"\r\n00042");
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(9));
+        assert!(matches!(codec.decode_eof(&mut buf), Err(DecimalCodecErr::MaxLengthExceeded)));
+        assert_eq!(codec.decode_eof(&mut buf).unwrap(), None);
+        assert!(buf.is_empty());
+
+        // "1234\r" may still be a full line
+        let mut buf = BytesMut::from(&b"1234\r"[..]);
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        buf.extend_from_slice(b"\n0042");
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(1234));
+        assert_eq!(codec.decode_eof(&mut buf).unwrap(), Some(42));
+    }
+}
@@ -0,0 +1,185 @@
+//! Struct-of-arrays decoding of fixed-width records into typed columns
+use crate::Biscuit;
+
+/// Byte range of a numeric field inside a fixed-width record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Column {
+    pub offset: usize,
+    pub width: usize,
+}
+
+impl Column {
+    pub fn new(offset: usize, width: usize) -> Self {
+        Column { offset, width }
+    }
+}
+
+/// Typed columns decoded from a buffer of fixed-width records
+///
+/// A row whose field failed to parse holds `T::default()` in that column
+/// and has its bit set in the error bitmap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnarBatch<T> {
+    columns: Vec<Vec<T>>,
+    error_bitmap: Vec<u64>,
+    rows: usize,
+}
+
+impl<T> ColumnarBatch<T> {
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// values of the `index`-th column, one per row
+    #[inline]
+    pub fn column(&self, index: usize) -> &[T] {
+        &self.columns[index]
+    }
+
+    #[inline]
+    pub fn columns(&self) -> &[Vec<T>] {
+        &self.columns
+    }
+
+    #[inline]
+    pub fn is_row_err(&self, row: usize) -> bool {
+        (self.error_bitmap[row >> 6] >> (row & 63)) & 1 == 1
+    }
+
+    /// one bit per row, row `i` is bit `i % 64` of word `i / 64`
+    #[inline]
+    pub fn error_bitmap(&self) -> &[u64] {
+        &self.error_bitmap
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.error_bitmap.iter().map(|w| w.count_ones() as usize).sum()
+    }
+}
+
+/// Struct-of-arrays decoder for fixed-width records
+///
+/// The buffer is walked column by column so that the same-width fields are
+/// fed to the parser back to back. A trailing partial record is ignored.
+///
+/// ```rust
+/// use biscuit_converter::columnar::{Column, ColumnarDecoder};
+///
+/// let buf = b"0001230042\
+///             0004560007\
+///             00078x0001";
+/// let decoder = ColumnarDecoder::new(10, &[Column::new(0, 6), Column::new(6, 4)]);
+/// let batch = decoder.decode::<i64>(buf);
+/// assert_eq!(batch.column(0), [123, 456, 0]);
+/// assert_eq!(batch.column(1), [42, 7, 1]);
+/// assert!(batch.is_row_err(2));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnarDecoder {
+    stride: usize,
+    columns: Vec<Column>,
+}
+
+impl ColumnarDecoder {
+    /// # Panics
+    /// Panics if `stride` is zero or a column does not fit in the record.
+    pub fn new(stride: usize, columns: &[Column]) -> Self {
+        assert!(stride > 0, "record stride must be positive");
+        for column in columns {
+            assert!(
+                column.offset + column.width <= stride,
+                "column {:?} exceeds the record stride {}", column, stride,
+            );
+        }
+        ColumnarDecoder { stride, columns: columns.to_vec() }
+    }
+
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    #[inline]
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    pub fn decode<T: Biscuit + Default + Copy>(&self, buf: &[u8]) -> ColumnarBatch<T> {
+        let rows = buf.len() / self.stride;
+        let mut error_bitmap = vec![0u64; rows.div_ceil(64)];
+        let mut columns = Vec::with_capacity(self.columns.len());
+
+        for column in self.columns.iter() {
+            let mut values = Vec::with_capacity(rows);
+            let mut start = column.offset;
+            for row in 0..rows {
+                let field = &buf[start..start + column.width];
+                match T::parse_decimal(field) {
+                    Ok(val) => values.push(val),
+                    Err(_) => {
+                        values.push(T::default());
+                        error_bitmap[row >> 6] |= 1 << (row & 63);
+                    }
+                }
+                start += self.stride;
+            }
+            columns.push(values);
+        }
+
+        ColumnarBatch { columns, error_bitmap, rows }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_columns() {
+        let mut buf = Vec::new();
+        for i in 0..200u64 {
+            buf.extend_from_slice(format!("{:08}|{:012}\n", i, i * 1_000_003).as_bytes());
+        }
+        let decoder = ColumnarDecoder::new(22, &[Column::new(0, 8), Column::new(9, 12)]);
+        let batch = decoder.decode::<u64>(&buf);
+
+        assert_eq!(batch.rows(), 200);
+        assert_eq!(batch.error_count(), 0);
+        for i in 0..200u64 {
+            assert_eq!(batch.column(0)[i as usize], i);
+            assert_eq!(batch.column(1)[i as usize], i * 1_000_003);
+        }
+    }
+
+    #[test]
+    fn test_error_bitmap() {
+        let mut buf = Vec::new();
+        for i in 0..130 {
+            if i % 65 == 3 {
+                buf.extend_from_slice(b"-00a1");
+            } else {
+                buf.extend_from_slice(format!("{:05}", -i).as_bytes());
+            }
+        }
+        // trailing partial record
+        buf.extend_from_slice(b"12");
+
+        let decoder = ColumnarDecoder::new(5, &[Column::new(0, 5)]);
+        let batch = decoder.decode::<i32>(&buf);
+
+        assert_eq!(batch.rows(), 130);
+        assert_eq!(batch.error_bitmap().len(), 3);
+        assert_eq!(batch.error_count(), 2);
+        assert!(batch.is_row_err(3));
+        assert!(batch.is_row_err(68));
+        assert_eq!(batch.column(0)[3], 0);
+        assert_eq!(batch.column(0)[129], -129);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_column_out_of_stride() {
+        let _ = ColumnarDecoder::new(8, &[Column::new(4, 5)]);
+    }
+}
@@ -0,0 +1,163 @@
+//! Calendar dates to days since 1970-01-01
+//!
+//! The eight digits of `YYYYMMDD` are validated and converted by one
+//! [`eight_to_u64`]; `YYYY-MM-DD` is packed to the same layout first. Dates are in the
+//! proleptic Gregorian calendar, years 0000 to 9999.
+//!
+//! ```rust
+//! use biscuit_converter::date::{parse_date_yyyymmdd, parse_date_iso};
+//! use biscuit_converter::error::TimeErr;
+//!
+//! assert_eq!(parse_date_yyyymmdd(b"19700101"), Ok(0));
+//! assert_eq!(parse_date_iso(b"2024-02-29"), Ok(19_782));
+//! assert_eq!(parse_date_iso(b"1969-12-31"), Ok(-1));
+//! assert_eq!(parse_date_yyyymmdd(b"20230229"), Err(TimeErr::Day));
+//! assert_eq!(parse_date_iso(b"2024-13-01"), Err(TimeErr::Month));
+//! ```
+use crate::error::TimeErr;
+use crate::little_endian_decimal::{check_decimal_bit_u64, eight_to_u64};
+
+// bytes 4 and 7 of "YYYY-MM-DD"
+const DASH_MASK: u64 = 0xFF00_00FF_0000_0000;
+const DASHES: u64 = 0x2D00_002D_0000_0000;
+
+// `is_multiple_of` needs rust 1.87, above the crate's `rust-version`
+#[inline]
+pub(crate) fn is_leap_year(year: u32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+#[inline]
+pub(crate) fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// days from 1970-01-01 to a valid date
+#[inline]
+pub(crate) fn days_from_civil(year: u32, month: u32, day: u32) -> i32 {
+    // the year starts in March so that the leap day is the last day of the year
+    let year = year as i32 - (month <= 2) as i32;
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month as i32 + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day as i32 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// inverse of [`days_from_civil`], (year, month, day)
+#[inline]
+pub(crate) fn civil_from_days(days: i32) -> (i32, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    (year_of_era + era * 400 + (month <= 2) as i32, month, day)
+}
+
+/// Days since 1970-01-01 from "YYYYMMDD" loaded little-endian
+#[inline]
+pub(crate) fn yyyymmdd_to_days(chunk: u64) -> Result<i32, TimeErr> {
+    if !check_decimal_bit_u64(chunk) {
+        return Err(TimeErr::NonDecimal);
+    }
+    let date = eight_to_u64(chunk) as u32;
+    let (year, month, day) = (date / 10_000, date / 100 % 100, date % 100);
+    if month == 0 || month > 12 {
+        return Err(TimeErr::Month);
+    }
+    if day == 0 || day > days_in_month(year, month) {
+        return Err(TimeErr::Day);
+    }
+    Ok(days_from_civil(year, month, day))
+}
+
+/// "YYYY-MM-DD" packed to "YYYYMMDD"
+#[inline]
+pub(crate) fn load_iso_date(u: &[u8; 10]) -> Result<u64, TimeErr> {
+    let chunk = u64::from_le_bytes(u[..8].try_into().unwrap());
+    if chunk & DASH_MASK != DASHES {
+        return Err(TimeErr::Separator);
+    }
+    let day = u16::from_le_bytes([u[8], u[9]]) as u64;
+    Ok((chunk & 0xFFFF_FFFF) | ((chunk >> 8) & 0xFFFF_0000_0000) | (day << 48))
+}
+
+/// `YYYYMMDD` to days since 1970-01-01
+pub fn parse_date_yyyymmdd(u: &[u8]) -> Result<i32, TimeErr> {
+    let u: &[u8; 8] = u.try_into().map_err(|_| TimeErr::Length)?;
+    yyyymmdd_to_days(u64::from_le_bytes(*u))
+}
+
+/// `YYYY-MM-DD` to days since 1970-01-01
+pub fn parse_date_iso(u: &[u8]) -> Result<i32, TimeErr> {
+    let u: &[u8; 10] = u.try_into().map_err(|_| TimeErr::Length)?;
+    yyyymmdd_to_days(load_iso_date(u)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // counts the days one by one from 0000-01-01, which is 719_528 days before the epoch
+    #[test]
+    fn test_every_day() {
+        let mut days = -719_528;
+        for year in 0..=9999 {
+            for month in 1..=12 {
+                for day in 1..=days_in_month(year, month) {
+                    let compact = format!("{:04}{:02}{:02}", year, month, day);
+                    assert_eq!(parse_date_yyyymmdd(compact.as_bytes()), Ok(days), "Failed for {}", compact);
+                    let iso = format!("{:04}-{:02}-{:02}", year, month, day);
+                    assert_eq!(parse_date_iso(iso.as_bytes()), Ok(days), "Failed for {}", iso);
+                    assert_eq!(civil_from_days(days), (year as i32, month, day));
+                    days += 1;
+                }
+            }
+        }
+        assert_eq!(parse_date_iso(b"9999-12-31"), Ok(2_932_896));
+    }
+
+    #[test]
+    fn test_ranges() {
+        assert_eq!(parse_date_yyyymmdd(b"20240001"), Err(TimeErr::Month));
+        assert_eq!(parse_date_yyyymmdd(b"20249901"), Err(TimeErr::Month));
+        assert_eq!(parse_date_yyyymmdd(b"20240100"), Err(TimeErr::Day));
+        assert_eq!(parse_date_yyyymmdd(b"20240132"), Err(TimeErr::Day));
+        assert_eq!(parse_date_yyyymmdd(b"20240431"), Err(TimeErr::Day));
+        assert_eq!(parse_date_iso(b"1900-02-29"), Err(TimeErr::Day));
+        assert_eq!(parse_date_iso(b"2000-02-29"), Ok(11_016));
+        assert_eq!(parse_date_iso(b"2100-02-29"), Err(TimeErr::Day));
+    }
+
+    #[test]
+    fn test_malformed() {
+        let valid = b"2024-09-10";
+        for i in 0..valid.len() {
+            for b in [b'/', b'-', b':', b' ', b'a'] {
+                let mut u = *valid;
+                if u[i] == b {
+                    continue;
+                }
+                u[i] = b;
+                let expected = match i {
+                    4 | 7 => TimeErr::Separator,
+                    _ => TimeErr::NonDecimal,
+                };
+                assert_eq!(parse_date_iso(&u), Err(expected), "Failed for {:?}", u);
+            }
+        }
+        assert_eq!(parse_date_yyyymmdd(b"2024-910"), Err(TimeErr::NonDecimal));
+        assert_eq!(parse_date_yyyymmdd(b"2024091"), Err(TimeErr::Length));
+        assert_eq!(parse_date_iso(b"20240910"), Err(TimeErr::Length));
+    }
+}
@@ -0,0 +1,260 @@
+//! EBCDIC decimal text, digits `0xF0`–`0xF9` and the minus sign `0x60`
+//!
+//! The digits are validated in place with the lane scheme of
+//! [`non_decimal_mask_u64`](crate::kernels::non_decimal_mask_u64), with complements of
+//! `0xF0` and `0xF9 + 1` instead of b'0' and b'9' + 1. The low nibble of an EBCDIC digit
+//! is its value, as in ascii, so the conversion kernels are shared.
+//!
+//! ```rust
+//! use biscuit_converter::ebcdic::parse_ebcdic_decimal;
+//! use biscuit_converter::error::ParseIntErr;
+//!
+//! assert_eq!(parse_ebcdic_decimal::<u32>(&[0xF1, 0xF2, 0xF3]), Ok(123));
+//! assert_eq!(parse_ebcdic_decimal::<i16>(&[0x60, 0xF4, 0xF2]), Ok(-42));
+//! assert_eq!(parse_ebcdic_decimal::<u32>(b"123"), Err(ParseIntErr::NonDecimal));
+//! ```
+use crate::SignMagnitude;
+use crate::error::ParseIntErr;
+use crate::little_endian_decimal::{eight_to_u64, sixteen_to_u128};
+
+pub const EBCDIC_ZERO: u8 = 0xF0;
+pub const EBCDIC_MINUS: u8 = 0x60;
+
+const TEN_POW_16: u128 = 10_000_000_000_000_000;
+
+const ZERO_COMPLEMENT_U16: u16 = 0x0010;
+const NINE_COMPLEMENT_U16: u16 = 0x0006;
+const CHECKER_MASK_U16: u16 = 0xFF00;
+const CARRY_MASK_U16: u16 = 0x0100;
+
+/// `true` iff both bytes of the chunk are EBCDIC digits
+///
+/// Each byte is split into its own 16-bit lane, adding the complement of `0xF0` (resp. `0xFA`)
+/// carries into the upper byte of the lane iff the byte is at least `0xF0` (resp. above `0xF9`).
+#[inline]
+#[must_use]
+pub fn check_ebcdic_bit_u16(chunk: u16) -> bool {
+    let lower = chunk & 0x00FF;
+    let upper = (chunk & 0xFF00) >> 8;
+
+    let nine_check = (((lower + NINE_COMPLEMENT_U16) | (upper + NINE_COMPLEMENT_U16)) & CHECKER_MASK_U16) == 0;
+    let zero_check = ((lower + ZERO_COMPLEMENT_U16) & (upper + ZERO_COMPLEMENT_U16) & CHECKER_MASK_U16) == CARRY_MASK_U16;
+
+    nine_check && zero_check
+}
+
+const ZERO_COMPLEMENT_U32: u32 = 0x00100010;
+const NINE_COMPLEMENT_U32: u32 = 0x00060006;
+const CHECKER_MASK_U32: u32 = 0xFF00FF00;
+const CARRY_MASK_U32: u32 = 0x01000100;
+
+/// `true` iff all four bytes of the chunk are EBCDIC digits
+#[inline]
+#[must_use]
+pub fn check_ebcdic_bit_u32(chunk: u32) -> bool {
+    let lower = chunk & 0x00FF00FF;
+    let upper = (chunk & 0xFF00FF00) >> 8;
+
+    let nine_check = (((lower + NINE_COMPLEMENT_U32) | (upper + NINE_COMPLEMENT_U32)) & CHECKER_MASK_U32) == 0;
+    let zero_check = ((lower + ZERO_COMPLEMENT_U32) & (upper + ZERO_COMPLEMENT_U32) & CHECKER_MASK_U32) == CARRY_MASK_U32;
+
+    nine_check && zero_check
+}
+
+const ZERO_COMPLEMENT_U64: u64 = 0x0010001000100010;
+const NINE_COMPLEMENT_U64: u64 = 0x0006000600060006;
+const CHECKER_MASK_U64: u64 = 0xFF00FF00FF00FF00;
+const CARRY_MASK_U64: u64 = 0x0100010001000100;
+
+/// `true` iff all eight bytes of the chunk are EBCDIC digits
+#[inline]
+#[must_use]
+pub fn check_ebcdic_bit_u64(chunk: u64) -> bool {
+    let lower = chunk & 0x00FF00FF00FF00FF;
+    let upper = (chunk & 0xFF00FF00FF00FF00) >> 8;
+
+    let nine_check = (((lower + NINE_COMPLEMENT_U64) | (upper + NINE_COMPLEMENT_U64)) & CHECKER_MASK_U64) == 0;
+    let zero_check = ((lower + ZERO_COMPLEMENT_U64) & (upper + ZERO_COMPLEMENT_U64) & CHECKER_MASK_U64) == CARRY_MASK_U64;
+
+    nine_check && zero_check
+}
+
+const ZERO_COMPLEMENT_U128: u128 = 0x00100010001000100010001000100010;
+const NINE_COMPLEMENT_U128: u128 = 0x00060006000600060006000600060006;
+const CHECKER_MASK_U128: u128 = 0xFF00FF00FF00FF00FF00FF00FF00FF00;
+const CARRY_MASK_U128: u128 = 0x01000100010001000100010001000100;
+
+/// `true` iff all sixteen bytes of the chunk are EBCDIC digits
+#[inline]
+#[must_use]
+pub fn check_ebcdic_bit_u128(chunk: u128) -> bool {
+    let lower = chunk & 0x00FF00FF00FF00FF00FF00FF00FF00FF;
+    let upper = (chunk & 0xFF00FF00FF00FF00FF00FF00FF00FF00) >> 8;
+
+    let nine_check = (((lower + NINE_COMPLEMENT_U128) | (upper + NINE_COMPLEMENT_U128)) & CHECKER_MASK_U128) == 0;
+    let zero_check = ((lower + ZERO_COMPLEMENT_U128) & (upper + ZERO_COMPLEMENT_U128) & CHECKER_MASK_U128) == CARRY_MASK_U128;
+
+    nine_check && zero_check
+}
+
+// left-padded with EBCDIC zeros, u.len() in 1..=8
+#[inline]
+fn le_ebcdic_to_u64(u: &[u8]) -> u64 {
+    let mut bytes = [EBCDIC_ZERO; 8];
+    bytes[8 - u.len()..].copy_from_slice(u);
+    u64::from_le_bytes(bytes)
+}
+
+// left-padded with EBCDIC zeros, u.len() in 1..=16
+#[inline]
+fn le_ebcdic_to_u128(u: &[u8]) -> u128 {
+    let mut bytes = [EBCDIC_ZERO; 16];
+    bytes[16 - u.len()..].copy_from_slice(u);
+    u128::from_le_bytes(bytes)
+}
+
+#[inline]
+fn checked_ebcdic_chunk(u: &[u8]) -> Result<u128, ParseIntErr> {
+    if u.len() <= 8 {
+        let chunk = le_ebcdic_to_u64(u);
+        if check_ebcdic_bit_u64(chunk) { Ok(eight_to_u64(chunk) as u128) } else { Err(ParseIntErr::NonDecimal) }
+    } else {
+        let chunk = le_ebcdic_to_u128(u);
+        if check_ebcdic_bit_u128(chunk) { Ok(sixteen_to_u128(chunk)) } else { Err(ParseIntErr::NonDecimal) }
+    }
+}
+
+/// Parses EBCDIC decimal text, a leading `0x60` is the minus sign
+///
+/// As with [`Biscuit::parse_decimal`](crate::Biscuit::parse_decimal), a minus sign is a
+/// non-digit byte for unsigned types, and text with more than `T::MAX_DIGITS` bytes after its
+/// leading zeros is `Overflow` (`NegOverflow` if negative) without its bytes being validated.
+/// `parse_decimal` validates some signed text before checking its length (shorter than 17 bytes,
+/// or 20 digits for `i64`), so a long signed input with a stray byte can be `NonDecimal` there.
+pub fn parse_ebcdic_decimal<T: SignMagnitude>(u: &[u8]) -> Result<T, ParseIntErr> {
+    let (neg, digits) = match u {
+        [EBCDIC_MINUS, rest @ ..] if T::IS_SIGNED => (true, rest),
+        _ => (false, u),
+    };
+    if digits.is_empty() {
+        return Err(ParseIntErr::Empty);
+    }
+    let zeros = digits.iter().take_while(|&&b| b == EBCDIC_ZERO).count().min(digits.len() - 1);
+    let digits = &digits[zeros..];
+    if digits.len() > T::MAX_DIGITS {
+        return Err(if neg { ParseIntErr::NegOverflow } else { ParseIntErr::Overflow });
+    }
+
+    // 16-digit chunks, the first one taking the remainder
+    let first_len = (digits.len() - 1) % 16 + 1;
+    let mut magnitude = Some(checked_ebcdic_chunk(&digits[..first_len])?);
+    for chunk in digits[first_len..].chunks_exact(16) {
+        let chunk = checked_ebcdic_chunk(chunk)?;
+        magnitude = magnitude.and_then(|val| val.checked_mul(TEN_POW_16)).and_then(|val| val.checked_add(chunk));
+    }
+    T::from_sign_magnitude(neg, magnitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Biscuit;
+
+    // ascii to EBCDIC for digits and '-', other bytes are left as they are
+    fn to_ebcdic(u: &[u8]) -> Vec<u8> {
+        u.iter()
+            .map(|&b| match b {
+                b'0'..=b'9' => b - b'0' + EBCDIC_ZERO,
+                b'-' => EBCDIC_MINUS,
+                _ => b,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_check_every_byte() {
+        for b in 0..=255u8 {
+            let is_digit = (0xF0..=0xF9).contains(&b);
+            for i in 0..16 {
+                let mut chunk = [EBCDIC_ZERO; 16];
+                chunk[i] = b;
+                if i < 2 {
+                    assert_eq!(check_ebcdic_bit_u16(u16::from_le_bytes(chunk[..2].try_into().unwrap())), is_digit);
+                }
+                if i < 4 {
+                    assert_eq!(check_ebcdic_bit_u32(u32::from_le_bytes(chunk[..4].try_into().unwrap())), is_digit);
+                }
+                if i < 8 {
+                    assert_eq!(check_ebcdic_bit_u64(u64::from_le_bytes(chunk[..8].try_into().unwrap())), is_digit);
+                }
+                assert_eq!(check_ebcdic_bit_u128(u128::from_le_bytes(chunk)), is_digit, "Failed for {:#04x} at {}", b, i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_against_ascii() {
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        for _ in 0..10_000 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let x = ((seed as i128) << 64 | seed as i128) >> (seed % 127);
+            let text = x.to_string();
+            let ebcdic = to_ebcdic(text.as_bytes());
+            assert_eq!(parse_ebcdic_decimal::<i128>(&ebcdic), Ok(x));
+            assert_eq!(parse_ebcdic_decimal::<i64>(&ebcdic), i64::parse_decimal(text.as_bytes()));
+            assert_eq!(parse_ebcdic_decimal::<i8>(&ebcdic), i8::parse_decimal(text.as_bytes()));
+            assert_eq!(parse_ebcdic_decimal::<u32>(&ebcdic), u32::parse_decimal(text.as_bytes()));
+            assert_eq!(parse_ebcdic_decimal::<u128>(&ebcdic), u128::parse_decimal(text.as_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_edges() {
+        for text in [
+            "", "-", "0", "-0", "00000000000000000000000000000000000000000000000007", "1-", "12 3", "--1",
+            "340282366920938463463374607431768211455", "340282366920938463463374607431768211456",
+            "1000000000000000000000000000000000000000", "-170141183460469231731687303715884105728",
+            "-170141183460469231731687303715884105729", "99999999999999999999999999999999999999999999999",
+        ] {
+            let ebcdic = to_ebcdic(text.as_bytes());
+            assert_eq!(parse_ebcdic_decimal::<u128>(&ebcdic), u128::parse_decimal(text.as_bytes()), "Failed for {:?}", text);
+            assert_eq!(parse_ebcdic_decimal::<i128>(&ebcdic), i128::parse_decimal(text.as_bytes()), "Failed for {:?}", text);
+        }
+        assert_eq!(parse_ebcdic_decimal::<u8>(&[0xF2, 0xF5, 0xF5]), Ok(u8::MAX));
+        assert_eq!(parse_ebcdic_decimal::<u8>(&[0xF2, 0xF5, 0xF6]), Err(ParseIntErr::Overflow));
+        assert_eq!(parse_ebcdic_decimal::<i8>(&[0x60, 0xF1, 0xF2, 0xF8]), Ok(i8::MIN));
+        assert_eq!(parse_ebcdic_decimal::<i8>(&[0x60, 0xF1, 0xF2, 0xF9]), Err(ParseIntErr::NegOverflow));
+        assert_eq!(parse_ebcdic_decimal::<u8>(&[0x60, 0xF0]), Err(ParseIntErr::NonDecimal));
+        assert_eq!(parse_ebcdic_decimal::<u32>(&[0xF1, 0xFA]), Err(ParseIntErr::NonDecimal));
+        assert_eq!(parse_ebcdic_decimal::<u32>(&[0x4E, 0xF1]), Err(ParseIntErr::NonDecimal));
+    }
+
+    #[test]
+    fn test_long_non_digits() {
+        let ebcdic = to_ebcdic(b"a8124197224048414");
+        assert_eq!(parse_ebcdic_decimal::<i32>(&ebcdic), i32::parse_decimal(b"a8124197224048414"));
+        assert_eq!(parse_ebcdic_decimal::<i32>(&ebcdic), Err(ParseIntErr::Overflow));
+        assert_eq!(parse_ebcdic_decimal::<i128>(&ebcdic), Err(ParseIntErr::NonDecimal));
+
+        // a stray byte anywhere in 17 to 60 bytes, parse_decimal checks their length first
+        for len in 17..=60usize {
+            for pos in [0, len / 2, len - 1] {
+                for stray in [b' ', b'a', b'+', b'-'] {
+                    let mut text = vec![b'7'; len];
+                    text[pos] = stray;
+                    let ebcdic = to_ebcdic(&text);
+                    assert_eq!(parse_ebcdic_decimal::<u8>(&ebcdic), u8::parse_decimal(&text), "Failed for {:?}", text);
+                    assert_eq!(parse_ebcdic_decimal::<u32>(&ebcdic), u32::parse_decimal(&text), "Failed for {:?}", text);
+                    assert_eq!(parse_ebcdic_decimal::<u128>(&ebcdic), u128::parse_decimal(&text), "Failed for {:?}", text);
+                    text[0] = b'-';
+                    let ebcdic = to_ebcdic(&text);
+                    assert_eq!(parse_ebcdic_decimal::<i16>(&ebcdic), i16::parse_decimal(&text), "Failed for {:?}", text);
+                    assert_eq!(parse_ebcdic_decimal::<i32>(&ebcdic), i32::parse_decimal(&text), "Failed for {:?}", text);
+                    assert_eq!(parse_ebcdic_decimal::<i128>(&ebcdic), i128::parse_decimal(&text), "Failed for {:?}", text);
+                }
+            }
+        }
+    }
+}
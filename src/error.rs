@@ -1,9 +1,13 @@
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ParseIntErr {
     NonDecimal,
     Empty,
     Overflow,
     NegOverflow,
+    LeadingZero,
+    NegativeZero,
+    Zero,
     Unknown,
 }
 
@@ -14,6 +18,9 @@ impl std::fmt::Display for ParseIntErr {
             ParseIntErr::Empty => write!(f, "Empty"),
             ParseIntErr::Overflow => write!(f, "Overflow"),
             ParseIntErr::NegOverflow => write!(f, "NegOverflow"),
+            ParseIntErr::LeadingZero => write!(f, "LeadingZero"),
+            ParseIntErr::NegativeZero => write!(f, "NegativeZero"),
+            ParseIntErr::Zero => write!(f, "Zero"),
             ParseIntErr::Unknown => write!(f, "UnknownError"),
         }
     }
@@ -36,6 +43,56 @@ impl ParseIntErr {
             ParseIntErr::Overflow => "Overflow",
             ParseIntErr::Unknown => "UnknownError",
             ParseIntErr::NegOverflow => "NegOverflow",
+            ParseIntErr::LeadingZero => "LeadingZero",
+            ParseIntErr::NegativeZero => "NegativeZero",
+            ParseIntErr::Zero => "Zero",
         }
     }
-}
\ No newline at end of file
+}
+/// Errors of the date and time parsers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeErr {
+    /// the input does not have the length of the layout
+    Length,
+    NonDecimal,
+    /// a separator (`:`, `.`, ...) is missing or misplaced
+    Separator,
+    Hour,
+    Minute,
+    Second,
+    Month,
+    /// day 0 or past the end of the month
+    Day,
+    /// `.` without digits, or more than nine digits after it
+    Fraction,
+    /// a UTC offset other than `Z` or `±HH:MM`
+    Offset,
+    /// the result does not fit in its integer type
+    OutOfRange,
+}
+
+impl std::fmt::Display for TimeErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::error::Error for TimeErr {}
+
+impl TimeErr {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TimeErr::Length => "Length",
+            TimeErr::NonDecimal => "NonDecimal",
+            TimeErr::Separator => "Separator",
+            TimeErr::Hour => "Hour",
+            TimeErr::Minute => "Minute",
+            TimeErr::Second => "Second",
+            TimeErr::Month => "Month",
+            TimeErr::Day => "Day",
+            TimeErr::Fraction => "Fraction",
+            TimeErr::Offset => "Offset",
+            TimeErr::OutOfRange => "OutOfRange",
+        }
+    }
+}
@@ -0,0 +1,374 @@
+//! FIX `tag=value` messages
+//!
+//! [`FixFields`] walks the SOH-terminated fields of a buffer, tags are parsed with the
+//! `u32` path of [`Biscuit`]. [`FixMessage`] keeps the fields of one message, checks the
+//! `9=` BodyLength and the `10=` CheckSum, and gives typed access to integer, price and
+//! UTCTimestamp values.
+//!
+//! ```rust
+//! use biscuit_converter::fix::{FixMessage, tag};
+//!
+//! let raw = b"8=FIX.4.2\x019=65\x0135=A\x0149=SERVER\x0156=CLIENT\x0134=177\x01\
+//!             52=20090107-18:15:16\x0198=0\x01108=30\x0110=062\x01";
+//! let msg = FixMessage::parse(raw).unwrap();
+//! assert_eq!(msg.get(tag::MSG_TYPE), Some(&b"A"[..]));
+//! assert_eq!(msg.int::<u64>(tag::MSG_SEQ_NUM), Ok(177));
+//! assert_eq!(msg.int::<u32>(108), Ok(30));
+//! assert_eq!(msg.utc_timestamp(tag::SENDING_TIME), Ok(1_231_352_116_000_000_000));
+//! ```
+use crate::Biscuit;
+use crate::error::{ParseIntErr, TimeErr};
+use crate::timestamp::parse_utc_timestamp;
+use std::fmt;
+use std::num::NonZeroU32;
+
+/// field terminator
+pub const SOH: u8 = 0x01;
+
+/// Tags of the standard header and trailer and of common integer fields
+pub mod tag {
+    pub const BEGIN_STRING: u32 = 8;
+    pub const BODY_LENGTH: u32 = 9;
+    pub const CHECK_SUM: u32 = 10;
+    pub const MSG_SEQ_NUM: u32 = 34;
+    pub const MSG_TYPE: u32 = 35;
+    pub const ORDER_QTY: u32 = 38;
+    pub const PRICE: u32 = 44;
+    pub const SENDING_TIME: u32 = 52;
+    pub const TRANSACT_TIME: u32 = 60;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixErr {
+    /// a field without `=` or without its SOH, starting at byte `offset`
+    Malformed { offset: usize },
+    /// a tag that is not a positive integer without leading zeros
+    Tag { offset: usize, err: ParseIntErr },
+    /// `8`, `9` or `10` is not where the standard header or trailer puts it
+    Misplaced { tag: u32 },
+    Missing { tag: u32 },
+    Value { tag: u32, err: ParseIntErr },
+    Timestamp { tag: u32, err: TimeErr },
+    BodyLength { declared: usize, actual: usize },
+    CheckSum { declared: u8, actual: u8 },
+}
+
+impl fmt::Display for FixErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixErr::Malformed { offset } => write!(f, "malformed field at byte {}", offset),
+            FixErr::Tag { offset, err } => write!(f, "invalid tag at byte {}: {}", offset, err),
+            FixErr::Misplaced { tag } => write!(f, "tag {} misplaced", tag),
+            FixErr::Missing { tag } => write!(f, "tag {} missing", tag),
+            FixErr::Value { tag, err } => write!(f, "invalid value of tag {}: {}", tag, err),
+            FixErr::Timestamp { tag, err } => write!(f, "invalid UTCTimestamp in tag {}: {}", tag, err),
+            FixErr::BodyLength { declared, actual } => write!(f, "body length {} declared, {} found", declared, actual),
+            FixErr::CheckSum { declared, actual } => write!(f, "checksum {:03} declared, {:03} computed", declared, actual),
+        }
+    }
+}
+
+impl std::error::Error for FixErr {}
+
+/// Iterator over the `(tag, value)` pairs of a buffer, it stops after the first error
+#[derive(Debug, Clone)]
+pub struct FixFields<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> FixFields<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        FixFields { buf, pos: 0 }
+    }
+
+    /// byte offset of the next field
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<'a> Iterator for FixFields<'a> {
+    type Item = Result<(u32, &'a [u8]), FixErr>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.pos;
+        let rest = &self.buf[offset..];
+        if rest.is_empty() {
+            return None;
+        }
+        // an error ends the iteration
+        self.pos = self.buf.len();
+
+        let equal = match rest.iter().position(|&b| b == b'=' || b == SOH) {
+            Some(equal) if rest[equal] == b'=' => equal,
+            _ => return Some(Err(FixErr::Malformed { offset })),
+        };
+        let tag = match NonZeroU32::parse_decimal_canonical(&rest[..equal]) {
+            Ok(tag) => tag.get(),
+            Err(err) => return Some(Err(FixErr::Tag { offset, err })),
+        };
+        let Some(length) = rest[equal + 1..].iter().position(|&b| b == SOH) else {
+            return Some(Err(FixErr::Malformed { offset }));
+        };
+        self.pos = offset + equal + 1 + length + 1;
+        Some(Ok((tag, &rest[equal + 1..equal + 1 + length])))
+    }
+}
+
+/// Decimal with `scale` fraction digits, i.e., `mantissa / 10^scale`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Price {
+    pub mantissa: i64,
+    pub scale: u32,
+}
+
+impl Price {
+    /// mantissa for another number of fraction digits, `None` if digits would be lost or on overflow
+    pub fn to_scale(self, scale: u32) -> Option<i64> {
+        if scale >= self.scale {
+            self.mantissa.checked_mul(10i64.checked_pow(scale - self.scale)?)
+        } else {
+            let div = 10i64.checked_pow(self.scale - scale)?;
+            if self.mantissa % div == 0 { Some(self.mantissa / div) } else { None }
+        }
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.mantissa as f64 / 10f64.powi(self.scale as i32)
+    }
+}
+
+/// Parses a FIX `float` (e.g., "-12.0450") keeping all of its fraction digits
+///
+/// ```rust
+/// use biscuit_converter::fix::{parse_price, Price};
+///
+/// assert_eq!(parse_price(b"-12.0450"), Ok(Price { mantissa: -120450, scale: 4 }));
+/// assert_eq!(parse_price(b"17"), Ok(Price { mantissa: 17, scale: 0 }));
+/// assert_eq!(parse_price(b"17.5").unwrap().to_scale(2), Some(1750));
+/// ```
+pub fn parse_price(u: &[u8]) -> Result<Price, ParseIntErr> {
+    let (neg, digits) = match u {
+        [b'-', rest @ ..] => (true, rest),
+        _ => (false, u),
+    };
+    let (int, frac) = match digits.iter().position(|&b| b == b'.') {
+        Some(dot) => (&digits[..dot], &digits[dot + 1..]),
+        None => (digits, &digits[digits.len()..]),
+    };
+    if int.is_empty() && frac.is_empty() {
+        return Err(ParseIntErr::Empty);
+    }
+
+    let int = if int.is_empty() { 0 } else { u64::parse_decimal(int)? };
+    let (frac_val, scale) = if frac.is_empty() { (0, 0) } else { (u64::parse_decimal(frac)?, frac.len() as u32) };
+    let abs = 10u64
+        .checked_pow(scale)
+        .and_then(|pow| int.checked_mul(pow))
+        .and_then(|val| val.checked_add(frac_val))
+        .ok_or(if neg { ParseIntErr::NegOverflow } else { ParseIntErr::Overflow })?;
+    let mantissa = if neg {
+        0i64.checked_sub_unsigned(abs).ok_or(ParseIntErr::NegOverflow)?
+    } else {
+        i64::try_from(abs).map_err(|_| ParseIntErr::Overflow)?
+    };
+    Ok(Price { mantissa, scale })
+}
+
+/// Fields of one message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixMessage<'a> {
+    buf: &'a [u8],
+    fields: Vec<(u32, &'a [u8])>,
+}
+
+impl<'a> FixMessage<'a> {
+    /// Splits the message into fields and checks the standard header and trailer,
+    /// the body length and the checksum
+    pub fn parse(buf: &'a [u8]) -> Result<Self, FixErr> {
+        let msg = Self::parse_unchecked(buf)?;
+        msg.check()?;
+        Ok(msg)
+    }
+
+    /// Splits the message into fields without any check beyond the `tag=value` syntax
+    pub fn parse_unchecked(buf: &'a [u8]) -> Result<Self, FixErr> {
+        let fields = FixFields::new(buf).collect::<Result<Vec<_>, _>>()?;
+        Ok(FixMessage { buf, fields })
+    }
+
+    fn check(&self) -> Result<(), FixErr> {
+        let n = self.fields.len();
+        let expect = |pos: usize, tag: u32| match self.fields.get(pos) {
+            None => Err(FixErr::Missing { tag }),
+            Some(&(found, _)) if found != tag => Err(FixErr::Misplaced { tag }),
+            Some(_) => Ok(()),
+        };
+        expect(0, tag::BEGIN_STRING)?;
+        expect(1, tag::BODY_LENGTH)?;
+        // the trailer is the last field and comes after the header
+        expect(n.max(3) - 1, tag::CHECK_SUM)?;
+
+        // the body runs from the field after 9= to the SOH before the CheckSum tag,
+        // taken from the scanner since the tag may be written with leading zeros (e.g. `010=`)
+        let mut scan = FixFields::new(self.buf);
+        scan.nth(1);
+        let body_start = scan.offset();
+        for _ in 2..n - 1 {
+            scan.next();
+        }
+        let trailer_start = scan.offset();
+        let declared = u32::parse_decimal(self.fields[1].1)
+            .map_err(|err| FixErr::Value { tag: tag::BODY_LENGTH, err })? as usize;
+        let actual = trailer_start - body_start;
+        if declared != actual {
+            return Err(FixErr::BodyLength { declared, actual });
+        }
+
+        let declared = match self.fields[n - 1].1 {
+            digits @ [_, _, _] => u8::parse_decimal(digits).map_err(|err| FixErr::Value { tag: tag::CHECK_SUM, err })?,
+            _ => return Err(FixErr::Value { tag: tag::CHECK_SUM, err: ParseIntErr::NonDecimal }),
+        };
+        let actual = checksum(&self.buf[..trailer_start]);
+        if declared != actual {
+            return Err(FixErr::CheckSum { declared, actual });
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn fields(&self) -> &[(u32, &'a [u8])] {
+        &self.fields
+    }
+
+    /// value of the first field with `tag`
+    pub fn get(&self, tag: u32) -> Option<&'a [u8]> {
+        self.fields.iter().find(|(t, _)| *t == tag).map(|(_, value)| *value)
+    }
+
+    fn require(&self, tag: u32) -> Result<&'a [u8], FixErr> {
+        self.get(tag).ok_or(FixErr::Missing { tag })
+    }
+
+    pub fn int<T: Biscuit>(&self, tag: u32) -> Result<T, FixErr> {
+        T::parse_decimal(self.require(tag)?).map_err(|err| FixErr::Value { tag, err })
+    }
+
+    pub fn price(&self, tag: u32) -> Result<Price, FixErr> {
+        parse_price(self.require(tag)?).map_err(|err| FixErr::Value { tag, err })
+    }
+
+    /// `YYYYMMDD-HH:MM:SS[.sss]` as nanoseconds since the Unix epoch
+    pub fn utc_timestamp(&self, tag: u32) -> Result<i64, FixErr> {
+        parse_utc_timestamp(self.require(tag)?).map_err(|err| FixErr::Timestamp { tag, err })
+    }
+}
+
+/// Sum of the bytes modulo 256, as in the `10=` CheckSum field
+pub fn checksum(u: &[u8]) -> u8 {
+    u.iter().fold(0u8, |acc, &b| acc.wrapping_add(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // builds a message from '|'-separated body fields, with a valid header and trailer
+    fn message(body: &str) -> Vec<u8> {
+        let body = body.replace('|', "\x01");
+        let mut msg = format!("8=FIX.4.4\x019={}\x01{}", body.len(), body).into_bytes();
+        let sum = checksum(&msg);
+        msg.extend_from_slice(format!("10={:03}\x01", sum).as_bytes());
+        msg
+    }
+
+    #[test]
+    fn test_known_message() {
+        let raw = b"8=FIX.4.2\x019=65\x0135=A\x0149=SERVER\x0156=CLIENT\x0134=177\x0152=20090107-18:15:16\x0198=0\x01108=30\x0110=062\x01";
+        let msg = FixMessage::parse(raw).unwrap();
+        assert_eq!(msg.fields().len(), 10);
+        assert_eq!(msg.int::<u32>(tag::BODY_LENGTH), Ok(65));
+        assert_eq!(msg.int::<u8>(98), Ok(0));
+        assert_eq!(msg.int::<u8>(tag::ORDER_QTY), Err(FixErr::Missing { tag: tag::ORDER_QTY }));
+    }
+
+    #[test]
+    fn test_typed_fields() {
+        let raw = message("35=D|34=4294967296|38=1500|44=-101.2500|60=20240229-23:59:59.123456|52=20230229-00:00:00|");
+        let msg = FixMessage::parse(&raw).unwrap();
+        assert_eq!(msg.int::<u64>(tag::MSG_SEQ_NUM), Ok(4_294_967_296));
+        assert_eq!(msg.int::<u32>(tag::MSG_SEQ_NUM), Err(FixErr::Value { tag: tag::MSG_SEQ_NUM, err: ParseIntErr::Overflow }));
+        assert_eq!(msg.int::<i32>(tag::ORDER_QTY), Ok(1500));
+        assert_eq!(msg.price(tag::PRICE), Ok(Price { mantissa: -1_012_500, scale: 4 }));
+        assert_eq!(msg.price(tag::PRICE).unwrap().to_scale(2), Some(-10_125));
+        assert_eq!(msg.price(tag::PRICE).unwrap().to_scale(1), None);
+        assert_eq!(msg.utc_timestamp(tag::TRANSACT_TIME), Ok(1_709_251_199_123_456_000));
+        assert_eq!(msg.utc_timestamp(tag::SENDING_TIME), Err(FixErr::Timestamp { tag: tag::SENDING_TIME, err: TimeErr::Day }));
+        assert_eq!(msg.utc_timestamp(tag::MSG_TYPE), Err(FixErr::Timestamp { tag: tag::MSG_TYPE, err: TimeErr::Length }));
+    }
+
+    #[test]
+    fn test_checks() {
+        let raw = message("35=0|34=2|");
+        assert!(FixMessage::parse(&raw).is_ok());
+
+        let mut bad_sum = raw.clone();
+        let pos = bad_sum.len() - 2;
+        bad_sum[pos] = if bad_sum[pos] == b'0' { b'1' } else { b'0' };
+        assert!(matches!(FixMessage::parse(&bad_sum), Err(FixErr::CheckSum { .. })));
+
+        let bad_length = String::from_utf8(raw.clone()).unwrap().replace("9=10", "9=11");
+        assert!(matches!(FixMessage::parse(bad_length.as_bytes()), Err(FixErr::BodyLength { declared: 11, actual: 10 })));
+
+        let no_trailer = &raw[..raw.len() - 7];
+        assert_eq!(FixMessage::parse(no_trailer), Err(FixErr::Misplaced { tag: tag::CHECK_SUM }));
+        assert_eq!(FixMessage::parse(&raw[10..]), Err(FixErr::Misplaced { tag: tag::BEGIN_STRING }));
+        assert!(FixMessage::parse_unchecked(no_trailer).is_ok());
+
+        // header and trailer reported by the tag that is actually missing or out of place
+        let header_only = b"8=FIX.4.4\x019=0\x01";
+        assert_eq!(FixMessage::parse(header_only), Err(FixErr::Missing { tag: tag::CHECK_SUM }));
+        assert_eq!(FixMessage::parse(&header_only[..10]), Err(FixErr::Missing { tag: tag::BODY_LENGTH }));
+        assert_eq!(FixMessage::parse(b""), Err(FixErr::Missing { tag: tag::BEGIN_STRING }));
+        assert_eq!(FixMessage::parse(b"8=FIX.4.4\x0135=0\x019=0\x0110=000\x01"), Err(FixErr::Misplaced { tag: tag::BODY_LENGTH }));
+
+        // a zero-padded tag is not CheckSum
+        let padded = String::from_utf8(raw.clone()).unwrap().replace("\x0110=", "\x01010=");
+        let offset = padded.find("010=").unwrap();
+        assert_eq!(FixMessage::parse(padded.as_bytes()), Err(FixErr::Tag { offset, err: ParseIntErr::LeadingZero }));
+    }
+
+    #[test]
+    fn test_malformed() {
+        let mut fields = FixFields::new(b"35=A\x0134\x0149=X\x01");
+        assert_eq!(fields.next(), Some(Ok((35, &b"A"[..]))));
+        assert_eq!(fields.next(), Some(Err(FixErr::Malformed { offset: 5 })));
+        assert_eq!(fields.next(), None);
+
+        let mut fields = FixFields::new(b"3a=A\x01");
+        assert_eq!(fields.next(), Some(Err(FixErr::Tag { offset: 0, err: ParseIntErr::NonDecimal })));
+        let mut fields = FixFields::new(b"35=A");
+        assert_eq!(fields.next(), Some(Err(FixErr::Malformed { offset: 0 })));
+        let mut fields = FixFields::new(b"035=A\x01");
+        assert_eq!(fields.next(), Some(Err(FixErr::Tag { offset: 0, err: ParseIntErr::LeadingZero })));
+        let mut fields = FixFields::new(b"35=A\x010=B\x01");
+        assert_eq!(fields.next(), Some(Ok((35, &b"A"[..]))));
+        assert_eq!(fields.next(), Some(Err(FixErr::Tag { offset: 5, err: ParseIntErr::Zero })));
+    }
+
+    #[test]
+    fn test_price() {
+        assert_eq!(parse_price(b"0.5"), Ok(Price { mantissa: 5, scale: 1 }));
+        assert_eq!(parse_price(b".5"), Ok(Price { mantissa: 5, scale: 1 }));
+        assert_eq!(parse_price(b"5."), Ok(Price { mantissa: 5, scale: 0 }));
+        assert_eq!(parse_price(b"-0.000001"), Ok(Price { mantissa: -1, scale: 6 }));
+        assert_eq!(parse_price(b"."), Err(ParseIntErr::Empty));
+        assert_eq!(parse_price(b"1.2.3"), Err(ParseIntErr::NonDecimal));
+        assert_eq!(parse_price(b"9223372036854775807"), Ok(Price { mantissa: i64::MAX, scale: 0 }));
+        assert_eq!(parse_price(b"-9223372036854775808"), Ok(Price { mantissa: i64::MIN, scale: 0 }));
+        assert_eq!(parse_price(b"922337203685477580.8"), Err(ParseIntErr::Overflow));
+        assert_eq!(parse_price(b"1.5").unwrap().to_f64(), 1.5);
+    }
+}
@@ -0,0 +1,220 @@
+//! Integer to ASCII decimal, the inverse of [`Biscuit`](crate::Biscuit)
+//!
+//! Digits are written right to left, two at a time from a lookup table, into a stack
+//! buffer large enough for `i128::MIN`.
+//!
+//! ```rust
+//! use biscuit_converter::format::DecimalBuffer;
+//!
+//! let mut buf = DecimalBuffer::new();
+//! assert_eq!(buf.format(1234u32), b"1234");
+//! assert_eq!(buf.format(i64::MIN), b"-9223372036854775808");
+//! ```
+use crate::sealed::Sealed;
+use std::num::{
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+};
+
+/// length of `i128::MIN` in decimal
+pub const DECIMAL_BUFFER_LEN: usize = 40;
+const TEN_POW_16: u128 = 10_000_000_000_000_000;
+
+const fn digit_pairs() -> [u8; 200] {
+    let mut table = [0u8; 200];
+    let mut i = 0;
+    while i < 100 {
+        table[2 * i] = b'0' + (i / 10) as u8;
+        table[2 * i + 1] = b'0' + (i % 10) as u8;
+        i += 1;
+    }
+    table
+}
+
+const DIGIT_PAIRS: [u8; 200] = digit_pairs();
+
+// writes `val` so that it ends right before `end`, returns where it starts
+#[inline]
+pub(crate) fn write_u64(mut val: u64, buf: &mut [u8], mut end: usize) -> usize {
+    while val >= 100 {
+        let pair = (val % 100) as usize * 2;
+        val /= 100;
+        end -= 2;
+        buf[end..end + 2].copy_from_slice(&DIGIT_PAIRS[pair..pair + 2]);
+    }
+    if val >= 10 {
+        let pair = val as usize * 2;
+        end -= 2;
+        buf[end..end + 2].copy_from_slice(&DIGIT_PAIRS[pair..pair + 2]);
+    } else {
+        end -= 1;
+        buf[end] = b'0' + val as u8;
+    }
+    end
+}
+
+// u128 is cut into 16-digit chunks so that the divisions stay in u64
+#[inline]
+fn write_u128(val: u128, buf: &mut [u8], end: usize) -> usize {
+    if val <= u64::MAX as u128 {
+        return write_u64(val as u64, buf, end);
+    }
+    let start = end - 16;
+    let digits_start = write_u64((val % TEN_POW_16) as u64, buf, end);
+    buf[start..digits_start].fill(b'0');
+    write_u128(val / TEN_POW_16, buf, start)
+}
+
+/// Integers that can be written in decimal notation
+///
+/// Sealed like [`Biscuit`](crate::Biscuit), the output of `format_decimal` is accepted
+/// back by `Biscuit::parse_decimal` of the same type.
+pub trait FormatDecimal: Copy + Sealed {
+    /// maximum number of bytes written, sign included
+    const MAX_LEN: usize;
+
+    /// writes the decimal at the end of `buf` and returns where it starts
+    #[doc(hidden)]
+    fn write_decimal(self, buf: &mut [u8; DECIMAL_BUFFER_LEN]) -> usize;
+}
+
+macro_rules! impl_format_unsigned {
+    ($($t:ty, $max_len:literal);* $(;)?) => {
+        $(
+            impl FormatDecimal for $t {
+                const MAX_LEN: usize = $max_len;
+
+                #[inline]
+                fn write_decimal(self, buf: &mut [u8; DECIMAL_BUFFER_LEN]) -> usize {
+                    write_u128(self as u128, buf, DECIMAL_BUFFER_LEN)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_format_signed {
+    ($($t:ty, $max_len:literal);* $(;)?) => {
+        $(
+            impl FormatDecimal for $t {
+                const MAX_LEN: usize = $max_len;
+
+                #[inline]
+                fn write_decimal(self, buf: &mut [u8; DECIMAL_BUFFER_LEN]) -> usize {
+                    let start = write_u128(self.unsigned_abs() as u128, buf, DECIMAL_BUFFER_LEN);
+                    if self < 0 {
+                        buf[start - 1] = b'-';
+                        start - 1
+                    } else {
+                        start
+                    }
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_format_non_zero {
+    ($($non_zero:ty => $t:ty),* $(,)?) => {
+        $(
+            impl FormatDecimal for $non_zero {
+                const MAX_LEN: usize = <$t as FormatDecimal>::MAX_LEN;
+
+                #[inline]
+                fn write_decimal(self, buf: &mut [u8; DECIMAL_BUFFER_LEN]) -> usize {
+                    self.get().write_decimal(buf)
+                }
+            }
+        )*
+    };
+}
+
+impl_format_unsigned!(u8, 3; u16, 5; u32, 10; u64, 20; u128, 39);
+impl_format_signed!(i8, 4; i16, 6; i32, 11; i64, 20; i128, 40);
+impl_format_non_zero!(
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroI128 => i128,
+);
+
+/// Stack buffer holding the last formatted integer
+#[derive(Debug, Clone, Copy)]
+pub struct DecimalBuffer {
+    bytes: [u8; DECIMAL_BUFFER_LEN],
+}
+
+impl Default for DecimalBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DecimalBuffer {
+    #[inline]
+    pub fn new() -> Self {
+        DecimalBuffer { bytes: [0; DECIMAL_BUFFER_LEN] }
+    }
+
+    #[inline]
+    pub fn format<T: FormatDecimal>(&mut self, val: T) -> &[u8] {
+        let start = val.write_decimal(&mut self.bytes);
+        &self.bytes[start..]
+    }
+
+    /// same as [`DecimalBuffer::format`], decimal digits are always valid UTF-8
+    #[inline]
+    pub fn format_str<T: FormatDecimal>(&mut self, val: T) -> &str {
+        let start = val.write_decimal(&mut self.bytes);
+        std::str::from_utf8(&self.bytes[start..]).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Biscuit;
+
+    #[test]
+    fn test_against_std() {
+        let mut buf = DecimalBuffer::new();
+        for x in [0u128, 1, 9, 10, 99, 100, 101, 999_999, u64::MAX as u128, u64::MAX as u128 + 1, TEN_POW_16 * TEN_POW_16, u128::MAX] {
+            assert_eq!(buf.format_str(x), x.to_string());
+            assert_eq!(buf.format_str(x as u64), (x as u64).to_string());
+            assert_eq!(buf.format_str(x as i128), (x as i128).to_string());
+            assert_eq!(buf.format_str(x as i64), (x as i64).to_string());
+            assert_eq!(buf.format_str(x as i8), (x as i8).to_string());
+        }
+        assert_eq!(buf.format_str(i128::MIN), i128::MIN.to_string());
+        assert_eq!(buf.format_str(i128::MIN).len(), i128::MAX_LEN);
+        assert_eq!(buf.format_str(NonZeroI16::MIN), "-32768");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut buf = DecimalBuffer::new();
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        for _ in 0..10_000 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let x = (seed as i128) << (seed % 64);
+            assert_eq!(i128::parse_decimal(buf.format(x)), Ok(x));
+            assert_eq!(u32::parse_decimal(buf.format(seed as u32)), Ok(seed as u32));
+        }
+    }
+}
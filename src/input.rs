@@ -0,0 +1,139 @@
+//! Inputs accepted by the [`Biscuit`] entry points
+//!
+//! Strings and byte containers are parsed directly, without `.as_bytes()` at the call site.
+//! A fixed-size array is dispatched on its length at compile time, so `u64::parse_decimal(b"12345678")`
+//! runs the 8-digit kernel without matching on the length. Signed types still dispatch at run time
+//! since the sign makes the number of digits unknown up front.
+//!
+//! ```rust
+//! use biscuit_converter::Biscuit;
+//! use std::borrow::Cow;
+//!
+//! assert_eq!(u32::parse_decimal("123"), Ok(123));
+//! assert_eq!(u32::parse_decimal(&String::from("123")), Ok(123));
+//! assert_eq!(u32::parse_decimal(&vec![b'1', b'2', b'3']), Ok(123));
+//! assert_eq!(u32::parse_decimal(&Cow::Borrowed("123")), Ok(123));
+//! assert_eq!(u32::parse_decimal(b"123"), Ok(123));
+//! ```
+//!
+//! The trait is sealed, the kernel picked for an input is not overridable outside this crate.
+//!
+//! ```compile_fail
+//! use biscuit_converter::AsDecimalInput;
+//!
+//! struct Field(Vec<u8>);
+//!
+//! impl AsDecimalInput for Field {
+//!     fn as_decimal_bytes(&self) -> &[u8] {
+//!         &self.0
+//!     }
+//! }
+//! ```
+use crate::Biscuit;
+use crate::error::ParseIntErr;
+use crate::sealed::Sealed;
+use std::borrow::Cow;
+
+pub trait AsDecimalInput: Sealed {
+    fn as_decimal_bytes(&self) -> &[u8];
+
+    #[doc(hidden)]
+    #[inline]
+    fn parse_input<T: Biscuit>(&self) -> Result<T, ParseIntErr> {
+        T::parse_decimal_slice(self.as_decimal_bytes())
+    }
+}
+
+impl Sealed for [u8] {}
+impl AsDecimalInput for [u8] {
+    #[inline]
+    fn as_decimal_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<const N: usize> Sealed for [u8; N] {}
+impl<const N: usize> AsDecimalInput for [u8; N] {
+    #[inline]
+    fn as_decimal_bytes(&self) -> &[u8] {
+        self
+    }
+
+    #[inline]
+    fn parse_input<T: Biscuit>(&self) -> Result<T, ParseIntErr> {
+        T::parse_decimal_array(self)
+    }
+}
+
+impl Sealed for str {}
+impl AsDecimalInput for str {
+    #[inline]
+    fn as_decimal_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Sealed for String {}
+impl AsDecimalInput for String {
+    #[inline]
+    fn as_decimal_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Sealed for Vec<u8> {}
+impl AsDecimalInput for Vec<u8> {
+    #[inline]
+    fn as_decimal_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+impl Sealed for Box<[u8]> {}
+impl AsDecimalInput for Box<[u8]> {
+    #[inline]
+    fn as_decimal_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<B: AsDecimalInput + ToOwned + ?Sized> Sealed for Cow<'_, B> {}
+impl<B: AsDecimalInput + ToOwned + ?Sized> AsDecimalInput for Cow<'_, B> {
+    #[inline]
+    fn as_decimal_bytes(&self) -> &[u8] {
+        self.as_ref().as_decimal_bytes()
+    }
+}
+
+impl<I: AsDecimalInput + ?Sized> Sealed for &I {}
+impl<I: AsDecimalInput + ?Sized> AsDecimalInput for &I {
+    #[inline]
+    fn as_decimal_bytes(&self) -> &[u8] {
+        (**self).as_decimal_bytes()
+    }
+
+    #[inline]
+    fn parse_input<T: Biscuit>(&self) -> Result<T, ParseIntErr> {
+        (**self).parse_input()
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Sealed for bytes::Bytes {}
+#[cfg(feature = "bytes")]
+impl AsDecimalInput for bytes::Bytes {
+    #[inline]
+    fn as_decimal_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Sealed for bytes::BytesMut {}
+#[cfg(feature = "bytes")]
+impl AsDecimalInput for bytes::BytesMut {
+    #[inline]
+    fn as_decimal_bytes(&self) -> &[u8] {
+        self
+    }
+}
@@ -1,55 +1,243 @@
 use crate::Biscuit;
+use crate::sealed::Sealed;
+use crate::unsigned_decimal::UnsignedCore;
+use crate::error::ParseIntErr;
+use crate::little_endian_decimal::{
+    eight_to_u64,
+    non_decimal_mask_u64,
+};
+
+const SIGN_TO_ZERO: u64 = (b'-' ^ b'0') as u64;
+const TEN_POW_8: u64 = 100_000_000;
+
+// loads N (1..=8) bytes like le_bytes_to_u64 and turns a leading b'-' into b'0' inside the word,
+// so "-1234" is read as the digits "01234" without re-slicing the input
+#[inline(always)]
+fn load_signed_u64<const N: usize>(u: &[u8]) -> (u64, bool) {
+    let mut bytes = [b'0'; 8];
+    bytes[8 - N..].copy_from_slice(&u[..N]);
+    let chunk = u64::from_le_bytes(bytes);
+    let neg = bytes[8 - N] == b'-';
+    (chunk ^ ((neg as u64 * SIGN_TO_ZERO) << (8 * (8 - N))), neg)
+}
+
+#[inline(always)]
+fn short_abs_n<const N: usize>(u: &[u8]) -> Result<(u64, bool), ParseIntErr> {
+    let (chunk, neg) = load_signed_u64::<N>(u);
+    if non_decimal_mask_u64(chunk) != 0 {
+        return Err(ParseIntErr::NonDecimal);
+    }
+    Ok((eight_to_u64(chunk), neg))
+}
+
+// absolute value and sign of 1..=5 bytes (a sign and up to four digits), one digit at a time:
+// at this length the padded load, the sign fold and the lane conversion cost more than the
+// digits themselves
+#[inline(always)]
+fn tiny_abs(u: &[u8]) -> Result<(u64, bool), ParseIntErr> {
+    let neg = u[0] == b'-';
+    let digits = &u[neg as usize..];
+    if digits.is_empty() {
+        return Err(ParseIntErr::Empty);
+    }
+    let mut abs = 0;
+    for &byte in digits {
+        let digit = byte.wrapping_sub(b'0');
+        if digit > 9 {
+            return Err(ParseIntErr::NonDecimal);
+        }
+        abs = abs * 10 + digit as u64;
+    }
+    Ok((abs, neg))
+}
+
+// absolute value and sign of 6..=8 bytes
+#[inline(always)]
+fn short_abs(u: &[u8]) -> Result<(u64, bool), ParseIntErr> {
+    match u.len() {
+        6 => short_abs_n::<6>(u),
+        7 => short_abs_n::<7>(u),
+        _ => short_abs_n::<8>(u),
+    }
+}
+
+// absolute value and sign of N + 8 bytes, the sign can only be in the upper chunk
+#[inline(always)]
+fn mid_abs_n<const N: usize>(u: &[u8]) -> Result<(u64, bool), ParseIntErr> {
+    let (upper_chunk, neg) = load_signed_u64::<N>(u);
+    let lower_chunk = u64::from_le_bytes(u[N..N + 8].try_into().unwrap());
+    if (non_decimal_mask_u64(upper_chunk) | non_decimal_mask_u64(lower_chunk)) != 0 {
+        return Err(ParseIntErr::NonDecimal);
+    }
+    Ok((eight_to_u64(upper_chunk) * TEN_POW_8 + eight_to_u64(lower_chunk), neg))
+}
+
+// absolute value and sign of 9..=16 bytes, out of line so that the short paths do not save
+// the registers it needs on every call
+#[inline(never)]
+fn mid_abs(u: &[u8]) -> Result<(u64, bool), ParseIntErr> {
+    match u.len() {
+        9 => mid_abs_n::<1>(u),
+        10 => mid_abs_n::<2>(u),
+        11 => mid_abs_n::<3>(u),
+        12 => mid_abs_n::<4>(u),
+        13 => mid_abs_n::<5>(u),
+        14 => mid_abs_n::<6>(u),
+        15 => mid_abs_n::<7>(u),
+        _ => mid_abs_n::<8>(u),
+    }
+}
+
+// 17 or more bytes go to the unsigned core, which works on a slice: the sign is not folded
+// into a load there but skipped with `neg as usize` as the start offset, a flag rather than
+// a branch. Folding it into the first chunk and combining that chunk with the rest costs a
+// power of ten and a wide multiply, which measured slower than the offset.
+#[inline]
+fn long_sign(u: &[u8]) -> (&[u8], bool) {
+    let neg = u[0] == b'-';
+    (&u[neg as usize..], neg)
+}
+
+// two's complement bits of the signed value, |MIN| is one more than MAX
+#[inline]
+fn apply_sign(abs: u64, neg: bool, max: u64) -> Result<u64, ParseIntErr> {
+    if abs > max + neg as u64 {
+        return Err(if neg { ParseIntErr::NegOverflow } else { ParseIntErr::Overflow });
+    }
+    let mask = (neg as u64).wrapping_neg();
+    Ok((abs ^ mask).wrapping_sub(mask))
+}
+
+#[inline]
+fn apply_sign_u128(abs: u128, neg: bool) -> Result<i128, ParseIntErr> {
+    if abs > i128::MAX as u128 + neg as u128 {
+        return Err(if neg { ParseIntErr::NegOverflow } else { ParseIntErr::Overflow });
+    }
+    let mask = (neg as u128).wrapping_neg();
+    Ok((abs ^ mask).wrapping_sub(mask) as i128)
+}
+
+// the unsigned core does not know about the sign
+#[inline]
+fn signed_err(err: ParseIntErr, neg: bool) -> ParseIntErr {
+    if neg && err == ParseIntErr::Overflow {
+        ParseIntErr::NegOverflow
+    } else {
+        err
+    }
+}
+
+impl Sealed for i128 {}
 
 impl Biscuit for i128 {
+    const MAX_DIGITS: usize = 39;
+    const IS_SIGNED: bool = true;
+
     #[inline]
-    fn parse_decimal(u: &[u8]) -> Result<Self, crate::error::ParseIntErr> {
-        if !u.is_empty() && u[0] == b'-' {
-            u128::unsinged_decimal_core(&u[1..], true, false).map(|val| (!(val as i128)).wrapping_add(1))
-        } else {
-            u128::unsinged_decimal_core(u, false, true).map(|val| val as i128)
+    fn parse_decimal_slice(u: &[u8]) -> Result<Self, ParseIntErr> {
+        match u.len() {
+            0 => Err(ParseIntErr::Empty),
+            1..=5 => tiny_abs(u).and_then(|(abs, neg)| apply_sign_u128(abs as u128, neg)),
+            6..=8 => short_abs(u).and_then(|(abs, neg)| apply_sign_u128(abs as u128, neg)),
+            9..=16 => mid_abs(u).and_then(|(abs, neg)| apply_sign_u128(abs as u128, neg)),
+            _ => {
+                let (digits, neg) = long_sign(u);
+                let abs = u128::unsigned_decimal_core(digits)
+                    .map_err(|err| signed_err(err, neg))?;
+                apply_sign_u128(abs, neg)
+            },
         }
     }
 }
+
+impl Sealed for i64 {}
+
 impl Biscuit for i64 {
+    const MAX_DIGITS: usize = 19;
+    const IS_SIGNED: bool = true;
+
     #[inline]
-    fn parse_decimal(u: &[u8]) -> Result<Self, crate::error::ParseIntErr> {
-        if !u.is_empty() && u[0] == b'-' {
-            u64::unsinged_decimal_core(&u[1..], true, false).map(|val| (!(val as i64)).wrapping_add(1))
-        } else {
-            u64::unsinged_decimal_core(u, false, true).map(|val| val as i64)
+    fn parse_decimal_slice(u: &[u8]) -> Result<Self, ParseIntErr> {
+        match u.len() {
+            0 => Err(ParseIntErr::Empty),
+            1..=5 => tiny_abs(u).and_then(|(abs, neg)| apply_sign(abs, neg, i64::MAX as u64)).map(|val| val as i64),
+            6..=8 => short_abs(u).and_then(|(abs, neg)| apply_sign(abs, neg, i64::MAX as u64)).map(|val| val as i64),
+            9..=16 => mid_abs(u).and_then(|(abs, neg)| apply_sign(abs, neg, i64::MAX as u64)).map(|val| val as i64),
+            _ => {
+                let (digits, neg) = long_sign(u);
+                let abs = u64::unsigned_decimal_core(digits)
+                    .map_err(|err| signed_err(err, neg))?;
+                apply_sign(abs, neg, i64::MAX as u64).map(|val| val as i64)
+            },
         }
     }
 }
 
+impl Sealed for i32 {}
+
 impl Biscuit for i32 {
+    const MAX_DIGITS: usize = 10;
+    const IS_SIGNED: bool = true;
+
     #[inline]
-    fn parse_decimal(u: &[u8]) -> Result<Self, crate::error::ParseIntErr> {
-        if !u.is_empty() && u[0] == b'-' {
-            u32::unsinged_decimal_core(&u[1..], true, false).map(|val| (!(val as i32)).wrapping_add(1))
-        } else {
-            u32::unsinged_decimal_core(u, false, true).map(|val| val as i32)
+    fn parse_decimal_slice(u: &[u8]) -> Result<Self, ParseIntErr> {
+        match u.len() {
+            0 => Err(ParseIntErr::Empty),
+            1..=5 => tiny_abs(u).and_then(|(abs, neg)| apply_sign(abs, neg, i32::MAX as u64)).map(|val| val as i32),
+            6..=8 => short_abs(u).and_then(|(abs, neg)| apply_sign(abs, neg, i32::MAX as u64)).map(|val| val as i32),
+            9..=16 => mid_abs(u).and_then(|(abs, neg)| apply_sign(abs, neg, i32::MAX as u64)).map(|val| val as i32),
+            _ => {
+                let (digits, neg) = long_sign(u);
+                let abs = u32::unsigned_decimal_core(digits)
+                    .map_err(|err| signed_err(err, neg))?;
+                apply_sign(abs as u64, neg, i32::MAX as u64).map(|val| val as i32)
+            },
         }
     }
 }
 
+impl Sealed for i16 {}
+
 impl Biscuit for i16 {
+    const MAX_DIGITS: usize = 5;
+    const IS_SIGNED: bool = true;
+
     #[inline]
-    fn parse_decimal(u: &[u8]) -> Result<Self, crate::error::ParseIntErr> {
-        if !u.is_empty() && u[0] == b'-' {
-            u16::unsinged_decimal_core(&u[1..], true, false).map(|val| (!(val as i16)).wrapping_add(1))
-        } else {
-            u16::unsinged_decimal_core(u, false, true).map(|val| val as i16)
+    fn parse_decimal_slice(u: &[u8]) -> Result<Self, ParseIntErr> {
+        match u.len() {
+            0 => Err(ParseIntErr::Empty),
+            1..=5 => tiny_abs(u).and_then(|(abs, neg)| apply_sign(abs, neg, i16::MAX as u64)).map(|val| val as i16),
+            6..=8 => short_abs(u).and_then(|(abs, neg)| apply_sign(abs, neg, i16::MAX as u64)).map(|val| val as i16),
+            9..=16 => mid_abs(u).and_then(|(abs, neg)| apply_sign(abs, neg, i16::MAX as u64)).map(|val| val as i16),
+            _ => {
+                let (digits, neg) = long_sign(u);
+                let abs = u16::unsigned_decimal_core(digits)
+                    .map_err(|err| signed_err(err, neg))?;
+                apply_sign(abs as u64, neg, i16::MAX as u64).map(|val| val as i16)
+            },
         }
     }
 }
 
+impl Sealed for i8 {}
+
 impl Biscuit for i8 {
+    const MAX_DIGITS: usize = 3;
+    const IS_SIGNED: bool = true;
+
     #[inline]
-    fn parse_decimal(u: &[u8]) -> Result<Self, crate::error::ParseIntErr> {
-        if !u.is_empty() && u[0] == b'-' {
-            u8::unsinged_decimal_core(u, true, false).map(|val| (!(val as i8)).wrapping_add(1))
-        } else {
-            u8::unsinged_decimal_core(u, false, true).map(|val| val as i8)
+    fn parse_decimal_slice(u: &[u8]) -> Result<Self, ParseIntErr> {
+        match u.len() {
+            0 => Err(ParseIntErr::Empty),
+            1..=5 => tiny_abs(u).and_then(|(abs, neg)| apply_sign(abs, neg, i8::MAX as u64)).map(|val| val as i8),
+            6..=8 => short_abs(u).and_then(|(abs, neg)| apply_sign(abs, neg, i8::MAX as u64)).map(|val| val as i8),
+            9..=16 => mid_abs(u).and_then(|(abs, neg)| apply_sign(abs, neg, i8::MAX as u64)).map(|val| val as i8),
+            _ => {
+                let (digits, neg) = long_sign(u);
+                let abs = u8::unsigned_decimal_core(digits)
+                    .map_err(|err| signed_err(err, neg))?;
+                apply_sign(abs as u64, neg, i8::MAX as u64).map(|val| val as i8)
+            },
         }
     }
 }
@@ -68,4 +256,61 @@ mod tests {
         
         Ok(())
     }
+
+    fn std_result<T: std::str::FromStr<Err = std::num::ParseIntError>>(input: &str) -> Result<T, ParseIntErr> {
+        input.parse::<T>().map_err(|e| match e.kind() {
+            std::num::IntErrorKind::PosOverflow => ParseIntErr::Overflow,
+            std::num::IntErrorKind::NegOverflow => ParseIntErr::NegOverflow,
+            _ => ParseIntErr::NonDecimal,
+        })
+    }
+
+    // inputs of 17 bytes or more, with the sign and leading zeros on either side of the first chunk
+    #[test]
+    fn test_short_inputs() -> Result<()> {
+        for length in 1..=8usize {
+            for valid in [&"98765432"[..length], &"-98765432"[..length]] {
+                for i in 0..length {
+                    for b in [b'/', b':', b'-', b' ', b'a'] {
+                        let mut input = valid.to_string().into_bytes();
+                        input[i] = b;
+                        let input = String::from_utf8(input)?;
+                        if input == "-" {
+                            assert_eq!(i32::parse_decimal(input.as_bytes()), Err(ParseIntErr::Empty));
+                            continue;
+                        }
+                        assert_eq!(i32::parse_decimal(input.as_bytes()), std_result::<i32>(&input), "Failed for {}", input);
+                        assert_eq!(i64::parse_decimal(input.as_bytes()), std_result::<i64>(&input), "Failed for {}", input);
+                        assert_eq!(i128::parse_decimal(input.as_bytes()), std_result::<i128>(&input), "Failed for {}", input);
+                    }
+                }
+                if valid != "-" {
+                    assert_eq!(i16::parse_decimal(valid.as_bytes()), std_result::<i16>(valid), "Failed for {}", valid);
+                    assert_eq!(i64::parse_decimal(valid.as_bytes()), std_result::<i64>(valid), "Failed for {}", valid);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_long_inputs() -> Result<()> {
+        for length in 17..=45usize {
+            for digits in ["9223372036854775807", "170141183460469231731687303715884105728", "128", "1"] {
+                for sign in ["", "-"] {
+                    let zeros = length.saturating_sub(sign.len() + digits.len());
+                    let input = format!("{}{}{}", sign, "0".repeat(zeros), digits);
+                    assert_eq!(i8::parse_decimal(input.as_bytes()), std_result::<i8>(&input), "Failed for {}", input);
+                    assert_eq!(i16::parse_decimal(input.as_bytes()), std_result::<i16>(&input), "Failed for {}", input);
+                    assert_eq!(i32::parse_decimal(input.as_bytes()), std_result::<i32>(&input), "Failed for {}", input);
+                    assert_eq!(i64::parse_decimal(input.as_bytes()), std_result::<i64>(&input), "Failed for {}", input);
+                    assert_eq!(i128::parse_decimal(input.as_bytes()), std_result::<i128>(&input), "Failed for {}", input);
+                }
+            }
+        }
+        assert_eq!(i64::parse_decimal(b"-1234567-123456789"), Err(ParseIntErr::NonDecimal));
+        assert_eq!(i64::parse_decimal(b"--12345678123456789"), Err(ParseIntErr::NonDecimal));
+        assert_eq!(i128::parse_decimal(b"-00000000000000000000000000000000000000000000000001"), Ok(-1));
+        Ok(())
+    }
 }
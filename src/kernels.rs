@@ -0,0 +1,117 @@
+//! Low-level SWAR building blocks used by the parsers
+//!
+//! ## Input layout
+//! Digits are loaded with `from_le_bytes`, so the first (most significant) digit sits in
+//! the lowest byte of the word. An input shorter than the word is left-padded with b'0'
+//! (see [`le_bytes_to_u64`] and friends), which does not change its value.
+//!
+//! The conversion kernels (`*_to_u*`) do not validate their input; run the matching
+//! `check_decimal_bit_*` first, or use the `checked_*` wrappers that take arrays.
+//!
+//! ```rust
+//! use biscuit_converter::kernels::{
+//!     check_decimal_bit_u64,
+//!     eight_to_u64,
+//!     le_bytes_to_u64,
+//!     checked_eight_to_u64,
+//! };
+//!
+//! let chunk = le_bytes_to_u64(b"20240910");
+//! assert!(check_decimal_bit_u64(chunk));
+//! assert_eq!(eight_to_u64(chunk), 20240910);
+//!
+//! let chunk = le_bytes_to_u64(b"123"); // "00000123"
+//! assert_eq!(eight_to_u64(chunk), 123);
+//!
+//! assert_eq!(checked_eight_to_u64(b"20240910"), Ok(20240910));
+//! assert!(checked_eight_to_u64(b"2024-09-").is_err());
+//! ```
+use crate::error::ParseIntErr;
+
+pub use crate::little_endian_decimal::{
+    two_to_u16_decimal,
+    four_to_u32,
+    eight_to_u64,
+    sixteen_to_u128,
+    check_decimal_bit_u16,
+    check_decimal_bit_u32,
+    check_decimal_bit_u64,
+    check_decimal_bit_u128,
+    non_decimal_mask_u32,
+    non_decimal_mask_u64,
+    non_decimal_mask_u128,
+};
+pub use crate::utils::{
+    le_bytes_to_u16,
+    le_bytes_to_u32,
+    le_bytes_to_u64,
+    le_bytes_to_u128,
+};
+
+#[inline]
+pub fn checked_two_to_u16(digits: &[u8; 2]) -> Result<u16, ParseIntErr> {
+    let chunk = u16::from_le_bytes(*digits);
+    if check_decimal_bit_u16(chunk) {
+        Ok(two_to_u16_decimal(chunk))
+    } else {
+        Err(ParseIntErr::NonDecimal)
+    }
+}
+
+#[inline]
+pub fn checked_four_to_u32(digits: &[u8; 4]) -> Result<u32, ParseIntErr> {
+    let chunk = u32::from_le_bytes(*digits);
+    if check_decimal_bit_u32(chunk) {
+        Ok(four_to_u32(chunk))
+    } else {
+        Err(ParseIntErr::NonDecimal)
+    }
+}
+
+#[inline]
+pub fn checked_eight_to_u64(digits: &[u8; 8]) -> Result<u64, ParseIntErr> {
+    let chunk = u64::from_le_bytes(*digits);
+    if check_decimal_bit_u64(chunk) {
+        Ok(eight_to_u64(chunk))
+    } else {
+        Err(ParseIntErr::NonDecimal)
+    }
+}
+
+#[inline]
+pub fn checked_sixteen_to_u128(digits: &[u8; 16]) -> Result<u128, ParseIntErr> {
+    let chunk = u128::from_le_bytes(*digits);
+    if check_decimal_bit_u128(chunk) {
+        Ok(sixteen_to_u128(chunk))
+    } else {
+        Err(ParseIntErr::NonDecimal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_kernels() {
+        assert_eq!(checked_two_to_u16(b"42"), Ok(42));
+        assert_eq!(checked_two_to_u16(b"4 "), Err(ParseIntErr::NonDecimal));
+        assert_eq!(checked_four_to_u32(b"0934"), Ok(934));
+        assert_eq!(checked_four_to_u32(b"09:4"), Err(ParseIntErr::NonDecimal));
+        assert_eq!(checked_eight_to_u64(b"99999999"), Ok(99_999_999));
+        assert_eq!(checked_eight_to_u64(b"9999999a"), Err(ParseIntErr::NonDecimal));
+        assert_eq!(checked_sixteen_to_u128(b"1234567890123456"), Ok(1_234_567_890_123_456));
+        assert_eq!(checked_sixteen_to_u128(b"123456789012345-"), Err(ParseIntErr::NonDecimal));
+    }
+
+    #[test]
+    fn test_left_padding() {
+        for i in 0..10_000u64 {
+            let s = i.to_string();
+            let u = s.as_bytes();
+            assert_eq!(four_to_u32(le_bytes_to_u32(u)) as u64, i);
+            assert_eq!(eight_to_u64(le_bytes_to_u64(u)), i);
+            assert_eq!(sixteen_to_u128(le_bytes_to_u128(u)) as u64, i);
+        }
+    }
+}
@@ -8,7 +8,7 @@
 //! 
 //! ```toml
 //! [dependencies]
-//! biscuit-converter = "0.3"
+//! biscuit-converter = "0.4"
 //! ```
 //! 
 //! Then, use it in your Rust code:
@@ -52,6 +52,7 @@
 //! This project is licensed under either of
 //! - Apache License, Version 2.0, <http://www.apache.org/licenses/LICENSE-2.0>
 //! - MIT license <http://opensource.org/licenses/MIT>
+//!
 //! at your option.
 //! 
 //! ## Contributions
@@ -64,16 +65,173 @@ pub mod little_endian_decimal;
 pub mod integer_decimal;
 pub mod error;
 pub mod utils;
+pub mod columnar;
+pub mod validate;
+pub mod kernels;
+pub mod wide;
+pub mod non_zero;
+pub mod input;
+pub mod reader;
+pub mod format;
+pub mod aggregate;
+pub mod fix;
+pub mod time;
+pub mod date;
+pub mod timestamp;
+pub mod bcd;
+pub mod sign_magnitude;
+pub mod zoned;
+pub mod ebcdic;
+pub mod big;
+#[cfg(feature = "tokio-util")]
+pub mod codec;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+
+pub use input::AsDecimalInput;
+pub use sign_magnitude::SignMagnitude;
+
+pub(crate) mod sealed {
+    /// `Biscuit`, `AsDecimalInput` and `FormatDecimal` are only implemented inside this crate
+    pub trait Sealed {}
+}
+
 /// Parser for decimal notation
 /// It can not parse scientific notation
-pub trait Biscuit: Sized {
+///
+/// The trait is sealed, it is implemented for the primitive integers, their `NonZero*`
+/// counterparts and the types in [`wide`].
+///
+/// ```compile_fail
+/// use biscuit_converter::{Biscuit, error::ParseIntErr};
+///
+/// struct Id(u32);
+///
+/// impl Biscuit for Id {
+///     const MAX_DIGITS: usize = 10;
+///     const IS_SIGNED: bool = false;
+///
+///     fn parse_decimal_slice(u: &[u8]) -> Result<Self, ParseIntErr> {
+///         u32::parse_decimal(u).map(Id)
+///     }
+/// }
+/// ```
+pub trait Biscuit: Sized + sealed::Sealed {
+    /// number of digits of `MAX` (and of `|MIN|` for signed types), without sign
+    const MAX_DIGITS: usize;
+    const IS_SIGNED: bool;
+
+    #[inline]
+    fn parse_decimal(input: &(impl AsDecimalInput + ?Sized)) -> Result<Self, error::ParseIntErr> {
+        input.parse_input()
+    }
+
+    /// Strict variant of [`Biscuit::parse_decimal`] accepting only the canonical form,
+    /// i.e., without redundant leading zeros and without "-0"
+    ///
+    /// ```rust
+    /// use biscuit_converter::{Biscuit, error::ParseIntErr};
+    ///
+    /// assert_eq!(i32::parse_decimal_canonical(b"123"), Ok(123));
+    /// assert_eq!(i32::parse_decimal_canonical(b"0"), Ok(0));
+    /// assert_eq!(i32::parse_decimal_canonical(b"0123"), Err(ParseIntErr::LeadingZero));
+    /// assert_eq!(i32::parse_decimal_canonical(b"-0123"), Err(ParseIntErr::LeadingZero));
+    /// assert_eq!(i32::parse_decimal_canonical(b"-0"), Err(ParseIntErr::NegativeZero));
+    /// ```
+    #[inline]
+    fn parse_decimal_canonical(input: &(impl AsDecimalInput + ?Sized)) -> Result<Self, error::ParseIntErr> {
+        let u = input.as_decimal_bytes();
+        let val = Self::parse_decimal_slice(u)?;
+        match u {
+            [b'-', b'0'] => Err(error::ParseIntErr::NegativeZero),
+            [b'0', _, ..] | [b'-', b'0', _, ..] => Err(error::ParseIntErr::LeadingZero),
+            _ => Ok(val),
+        }
+    }
+
+    /// Same as [`Biscuit::parse_decimal`] but without classifying the error
+    ///
+    /// ```rust
+    /// use biscuit_converter::Biscuit;
+    ///
+    /// assert_eq!(u64::try_parse_decimal(b"1234"), Some(1234));
+    /// assert_eq!(u64::try_parse_decimal(b"12a4"), None);
+    /// assert_eq!(i8::try_parse_decimal(b"-128"), Some(-128));
+    /// assert_eq!(i8::try_parse_decimal(b"128"), None);
+    /// ```
+    #[inline]
+    fn try_parse_decimal(input: &(impl AsDecimalInput + ?Sized)) -> Option<Self> {
+        Self::try_parse_decimal_slice(input.as_decimal_bytes())
+    }
+
+    #[doc(hidden)]
+    fn parse_decimal_slice(u: &[u8]) -> Result<Self, error::ParseIntErr>;
+
+    #[doc(hidden)]
+    #[inline]
+    fn try_parse_decimal_slice(u: &[u8]) -> Option<Self> {
+        Self::parse_decimal_slice(u).ok()
+    }
+
+    /// inputs of a length known at compile time
+    #[doc(hidden)]
     #[inline]
-    fn parse_decimal(u: &[u8]) -> Result<Self, error::ParseIntErr> {
-        Self::unsinged_decimal_core(u, false, false)
+    fn parse_decimal_array<const N: usize>(u: &[u8; N]) -> Result<Self, error::ParseIntErr> {
+        Self::parse_decimal_slice(u)
     }
 
-    fn unsinged_decimal_core(_u: &[u8], _neg_max_check: bool, _pos_max_check: bool) -> Result<Self, error::ParseIntErr> {
-        unimplemented!("This function should be implemented in the child struct")
+    /// 0.3 entry point of the unsigned core
+    ///
+    /// On unsigned types the flags check the digits against `|MIN|` (`neg_max_check`) and `MAX`
+    /// (`pos_max_check`) of the signed counterpart, as in 0.3. Signed types did not implement it
+    /// in 0.3 (the call panicked); they now parse their own signed range and **ignore both flags**.
+    #[deprecated(
+        since = "0.4.0",
+        note = "use `Biscuit::parse_decimal` or `biscuit_converter::parse`; on signed types the bound flags are ignored",
+    )]
+    #[doc(hidden)]
+    #[inline]
+    fn unsinged_decimal_core(u: &[u8], _neg_max_check: bool, _pos_max_check: bool) -> Result<Self, error::ParseIntErr> {
+        Self::parse_decimal_slice(u)
+    }
+}
+
+/// Parses `input` as `T`, the same as `T::parse_decimal(u)`
+///
+/// ```rust
+/// use biscuit_converter::parse;
+///
+/// assert_eq!(parse::<u32>(b"123"), Ok(123));
+/// assert_eq!(parse::<i8>(b"-128"), Ok(-128));
+/// ```
+#[inline]
+pub fn parse<T: Biscuit>(input: &(impl AsDecimalInput + ?Sized)) -> Result<T, error::ParseIntErr> {
+    T::parse_decimal(input)
+}
+
+/// Method-call form of [`parse`] on every [`AsDecimalInput`]
+///
+/// ```rust
+/// use biscuit_converter::ParseDecimalExt;
+///
+/// assert_eq!(b"123".parse_decimal::<u32>(), Ok(123));
+/// assert_eq!(b"12a".try_parse_decimal::<u32>(), None);
+/// assert_eq!("-42".parse_decimal::<i64>(), Ok(-42));
+/// ```
+pub trait ParseDecimalExt {
+    fn parse_decimal<T: Biscuit>(&self) -> Result<T, error::ParseIntErr>;
+    fn try_parse_decimal<T: Biscuit>(&self) -> Option<T>;
+}
+
+impl<I: AsDecimalInput + ?Sized> ParseDecimalExt for I {
+    #[inline]
+    fn parse_decimal<T: Biscuit>(&self) -> Result<T, error::ParseIntErr> {
+        self.parse_input()
+    }
+
+    #[inline]
+    fn try_parse_decimal<T: Biscuit>(&self) -> Option<T> {
+        T::try_parse_decimal_slice(self.as_decimal_bytes())
     }
 }
 
@@ -98,6 +256,12 @@ mod tests {
         let nondecimal = i32::parse_decimal(b"a123");
         assert_eq!(nondecimal, Err(ParseIntErr::NonDecimal));
 
+        let nondecimal = u64::parse_decimal(b"1234 678");
+        assert_eq!(nondecimal, Err(ParseIntErr::NonDecimal));
+
+        let nondecimal = u32::parse_decimal(b"1/");
+        assert_eq!(nondecimal, Err(ParseIntErr::NonDecimal));
+
         let err_empty = i32::parse_decimal(b"");
         assert_eq!(err_empty, Err(ParseIntErr::Empty));
 
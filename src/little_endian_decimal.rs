@@ -57,16 +57,20 @@ pub(crate) fn checked_conversion_u128(input: &[u8]) -> Result<u128, ParseIntErr>
 }
 
 
+/// Two ascii digits loaded little-endian (`u16::from_le_bytes(*b"12")`) to their value.
+/// The digits are not validated.
 #[inline]
 #[must_use]
-pub(crate) fn two_to_u16_decimal(chunk: u16) -> u16 {
+pub fn two_to_u16_decimal(chunk: u16) -> u16 {
     ((chunk & 0x0f00) >> 8) + (chunk & 0x000f) * 10
 }
 
 
+/// Four ascii digits loaded little-endian (`u32::from_le_bytes(*b"1234")`) to their value.
+/// The digits are not validated.
 #[inline]
 #[must_use]
-pub(crate) fn four_to_u32(mut chunk: u32) -> u32 {
+pub fn four_to_u32(mut chunk: u32) -> u32 {
     //chunk <<= 32 - length * 8;
     let lower_digits = (chunk & 0x0f000f00) >> 8;
     let upper_digits = (chunk & 0x000f000f) * 10;
@@ -80,9 +84,11 @@ pub(crate) fn four_to_u32(mut chunk: u32) -> u32 {
     chunk
 }
 
+/// Eight ascii digits loaded little-endian (`u64::from_le_bytes(*b"12345678")`) to their value.
+/// The digits are not validated.
 #[inline]
 #[must_use]
-pub(crate) fn eight_to_u64(mut chunk: u64) -> u64 {
+pub fn eight_to_u64(mut chunk: u64) -> u64 {
     //chunk <<= 64 - length * 8;
 
     let lower_digits = (chunk & 0x0f000f000f000f00) >> 8;
@@ -102,9 +108,11 @@ pub(crate) fn eight_to_u64(mut chunk: u64) -> u64 {
     chunk
 }
 
+/// Sixteen ascii digits loaded little-endian to their value.
+/// The digits are not validated.
 #[inline]
 #[must_use]
-pub(crate) fn sixteen_to_u128(mut chunk: u128) -> u128 {
+pub fn sixteen_to_u128(mut chunk: u128) -> u128 {
     let lower_digits = (chunk & 0x0f000f000f000f000f000f000f000f00) >> 8;
     let upper_digits = (chunk & 0x000f000f000f000f000f000f000f000f) * 10;
     chunk = lower_digits + upper_digits;
@@ -124,68 +132,96 @@ pub(crate) fn sixteen_to_u128(mut chunk: u128) -> u128 {
     chunk
 }
 
-const ZERO_COMPLEMENT_U16: u16 = 0x00CF;
-const NINE_COMPLEMENT_U16: u16 = 0x00C6;
-const CHECKER_MASK_U16: u16 = 0xFF00;
-
+/// `true` iff both bytes of the chunk are ascii digits
+///
+/// A digit has the high nibble 3 and keeps it when 6 is added to the byte, while b':'
+/// to b'?' move up to b'@' to b'E'. A byte with another high nibble fails on its own,
+/// so the carry it may push into the next byte does not matter.
 #[inline]
 #[must_use]
-pub(crate) fn check_decimal_bit_u16(chunk: u16) -> bool {
-    let lower_upper_check = (((chunk & 0x00FF) + NINE_COMPLEMENT_U16) & CHECKER_MASK_U16) == 0;
-    let lower_lower_check = (((0x00FF - (chunk & 0x00FF)) + ZERO_COMPLEMENT_U16) & CHECKER_MASK_U16) != 0;
-    
-    let upper_upper_check = ((((chunk & 0xFF00) >> 8) + NINE_COMPLEMENT_U16) & CHECKER_MASK_U16) == 0;
-    let upper_lower_check = (((0xFF00 - ((chunk & 0xFF00) >> 8 )) + ZERO_COMPLEMENT_U16) & CHECKER_MASK_U16) != 0;
-
-    lower_upper_check && lower_lower_check && upper_upper_check && upper_lower_check
+pub fn check_decimal_bit_u16(chunk: u16) -> bool {
+    let high = chunk & 0xF0F0;
+    let high_plus_six = (chunk.wrapping_add(0x0606) & 0xF0F0) >> 4;
+    high | high_plus_six == 0x3333
 }
 
-const ZERO_COMPLEMENT_U32: u32 = 0x00CF00CF;
+const ZERO_COMPLEMENT_U32: u32 = 0x00D000D0;
 const NINE_COMPLEMENT_U32: u32 = 0x00C600C6;
-const CHECKER_MASK_U32: u32 = 0xFF00FF00;
+const CARRY_MASK_U32: u32 = 0x01000100;
 
+/// Four-byte version of [`check_decimal_bit_u16`]
 #[inline]
 #[must_use]
-pub(crate) fn check_decimal_bit_u32(chunk: u32) -> bool {
-    let lower_upper_check = (((chunk & 0x00FF00FF) + NINE_COMPLEMENT_U32) & CHECKER_MASK_U32) == 0;
-    let lower_lower_check = (((0x00FF00FF - (chunk & 0x00FF00FF)) + ZERO_COMPLEMENT_U32) & CHECKER_MASK_U32) != 0;
-    
-    let upper_upper_check = ((((chunk & 0xFF00FF00) >> 8) + NINE_COMPLEMENT_U32) & CHECKER_MASK_U32) == 0;
-    let upper_lower_check = (((0xFF00FF00 - ((chunk & 0xFF00FF00) >> 8 )) + ZERO_COMPLEMENT_U32) & CHECKER_MASK_U32) != 0;
+pub fn check_decimal_bit_u32(chunk: u32) -> bool {
+    let high = chunk & 0xF0F0F0F0;
+    let high_plus_six = (chunk.wrapping_add(0x06060606) & 0xF0F0F0F0) >> 4;
+    high | high_plus_six == 0x33333333
+}
 
-    lower_upper_check && lower_lower_check && upper_upper_check && upper_lower_check
+/// Four-byte version of [`non_decimal_mask_u64`]
+#[inline]
+#[must_use]
+pub fn non_decimal_mask_u32(chunk: u32) -> u32 {
+    let lower = chunk & 0x00FF00FF;
+    let upper = (chunk & 0xFF00FF00) >> 8;
+
+    let lower_mask = ((lower + NINE_COMPLEMENT_U32) | !(lower + ZERO_COMPLEMENT_U32)) & CARRY_MASK_U32;
+    let upper_mask = ((upper + NINE_COMPLEMENT_U32) | !(upper + ZERO_COMPLEMENT_U32)) & CARRY_MASK_U32;
+
+    (lower_mask >> 8) | upper_mask
 }
 
-const ZERO_COMPLEMENT_U64: u64 = 0x00CF00CF00CF00CF;
+const ZERO_COMPLEMENT_U64: u64 = 0x00D000D000D000D0;
 const NINE_COMPLEMENT_U64: u64 = 0x00C600C600C600C6;
-const CHECKER_MASK_U64: u64 = 0xFF00FF00FF00FF00;
+const CARRY_MASK_U64: u64 = 0x0100010001000100;
 
+/// Eight-byte version of [`check_decimal_bit_u16`]
 #[inline]
 #[must_use]
-pub(crate) fn check_decimal_bit_u64(chunk: u64) -> bool {
-    let lower_upper_check = (((chunk & 0x00FF00FF00FF00FF) + NINE_COMPLEMENT_U64) & CHECKER_MASK_U64) == 0;
-    let lower_lower_check = (((0x00FF00FF00FF00FF - (chunk & 0x00FF00FF00FF00FF)) + ZERO_COMPLEMENT_U64) & CHECKER_MASK_U64) != 0;
-    
-    let upper_upper_check = ((((chunk & 0xFF00FF00FF00FF00) >> 8) + NINE_COMPLEMENT_U64) & CHECKER_MASK_U64) == 0;
-    let upper_lower_check = (((0xFF00FF00FF00FF00 - ((chunk & 0xFF00FF00FF00FF00) >> 8 )) + ZERO_COMPLEMENT_U64) & CHECKER_MASK_U64) != 0;
+pub fn check_decimal_bit_u64(chunk: u64) -> bool {
+    let high = chunk & 0xF0F0F0F0F0F0F0F0;
+    let high_plus_six = (chunk.wrapping_add(0x0606060606060606) & 0xF0F0F0F0F0F0F0F0) >> 4;
+    high | high_plus_six == 0x3333333333333333
+}
+
+/// Byte `i` of the result is `0x01` iff byte `i` of the chunk is not an ascii digit,
+/// so `mask.trailing_zeros() / 8` is the index of the first non-digit.
+#[inline]
+#[must_use]
+pub fn non_decimal_mask_u64(chunk: u64) -> u64 {
+    let lower = chunk & 0x00FF00FF00FF00FF;
+    let upper = (chunk & 0xFF00FF00FF00FF00) >> 8;
+
+    let lower_mask = ((lower + NINE_COMPLEMENT_U64) | !(lower + ZERO_COMPLEMENT_U64)) & CARRY_MASK_U64;
+    let upper_mask = ((upper + NINE_COMPLEMENT_U64) | !(upper + ZERO_COMPLEMENT_U64)) & CARRY_MASK_U64;
 
-    lower_upper_check && lower_lower_check && upper_upper_check && upper_lower_check
+    (lower_mask >> 8) | upper_mask
 }
 
-const ZERO_COMPLEMENT_U128: u128 = 0x00CF00CF00CF00CF00CF00CF00CF00CF;
+const ZERO_COMPLEMENT_U128: u128 = 0x00D000D000D000D000D000D000D000D0;
 const NINE_COMPLEMENT_U128: u128 = 0x00C600C600C600C600C600C600C600C6;
-const CHECKER_MASK_U128: u128 = 0xFF00FF00FF00FF00FF00FF00FF00FF00;
+const CARRY_MASK_U128: u128 = 0x01000100010001000100010001000100;
 
+/// Sixteen-byte version of [`check_decimal_bit_u16`]
 #[inline]
 #[must_use]
-pub(crate) fn check_decimal_bit_u128(chunk: u128) -> bool {
-    let lower_upper_check = (((chunk & 0x00FF00FF00FF00FF00FF00FF00FF00FF) + NINE_COMPLEMENT_U128) & CHECKER_MASK_U128) == 0;
-    let lower_lower_check = (((0x00FF00FF00FF00FF00FF00FF00FF00FF - (chunk & 0x00FF00FF00FF00FF00FF00FF00FF00FF)) + ZERO_COMPLEMENT_U128) & CHECKER_MASK_U128) != 0;
-    
-    let upper_upper_check = ((((chunk & 0xFF00FF00FF00FF00FF00FF00FF00FF00) >> 8) + NINE_COMPLEMENT_U128) & CHECKER_MASK_U128) == 0;
-    let upper_lower_check = (((0xFF00FF00FF00FF00FF00FF00FF00FF00 - ((chunk & 0xFF00FF00FF00FF00FF00FF00FF00FF00) >> 8 )) + ZERO_COMPLEMENT_U128) & CHECKER_MASK_U128) != 0;
+pub fn check_decimal_bit_u128(chunk: u128) -> bool {
+    let high = chunk & 0xF0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0;
+    let high_plus_six = (chunk.wrapping_add(0x06060606060606060606060606060606) & 0xF0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0) >> 4;
+    high | high_plus_six == 0x33333333333333333333333333333333
+}
+
+/// Sixteen-byte version of [`non_decimal_mask_u64`]
+#[inline]
+#[must_use]
+pub fn non_decimal_mask_u128(chunk: u128) -> u128 {
+    let lower = chunk & 0x00FF00FF00FF00FF00FF00FF00FF00FF;
+    let upper = (chunk & 0xFF00FF00FF00FF00FF00FF00FF00FF00) >> 8;
 
-    lower_upper_check && lower_lower_check && upper_upper_check && upper_lower_check
+    let lower_mask = ((lower + NINE_COMPLEMENT_U128) | !(lower + ZERO_COMPLEMENT_U128)) & CARRY_MASK_U128;
+    let upper_mask = ((upper + NINE_COMPLEMENT_U128) | !(upper + ZERO_COMPLEMENT_U128)) & CARRY_MASK_U128;
+
+    (lower_mask >> 8) | upper_mask
 }
 
 #[cfg(test)]
@@ -193,19 +229,21 @@ mod tests {
     use super::*;
 
     pub(crate) fn check_decimal(input: &[u8]) -> bool {
-        input.iter().all(|&x| (b'0'..=b'9').contains(&x))
+        input.iter().all(|&x| x.is_ascii_digit())
     }
     
+    #[allow(dead_code)]
     #[inline]
     #[must_use]
     pub(crate) fn check_decimal_bit_u8(chunk: u8) -> bool {
         (0x30..=0x39).contains(&chunk)
     }
 
+    #[allow(dead_code)]
     #[inline]
     #[must_use]
     pub(crate) fn one_to_u8(chunk: u8) -> u8 {
-        if chunk < 0x30 || chunk > 0x39 {
+        if !(0x30..=0x39).contains(&chunk) {
             return 0;
         }
         chunk - 0x30    
@@ -220,11 +258,15 @@ mod tests {
             assert_eq!(check_decimal_bit_u16(chunk), check_decimal(u));
         }
 
-        let u_vec = [b"1x", b"x1", b"ab", b"zy"];
+        let u_vec = [b"1x", b"x1", b"ab", b"zy", b" 1", b"1/", b"-1", b"1\0"];
         for u in u_vec.iter() {
             let chunk = le_bytes_to_u16(*u);
             assert_eq!(check_decimal_bit_u16(chunk), check_decimal(*u));
         }
+
+        for chunk in 0..=u16::MAX {
+            assert_eq!(check_decimal_bit_u16(chunk), check_decimal(&chunk.to_le_bytes()));
+        }
     }
 
     #[test]
@@ -236,7 +278,7 @@ mod tests {
             assert_eq!(check_decimal_bit_u32(chunk), check_decimal(u));
         }
 
-        let u_vec = [b"1x1x", b"x11a", b"ab11", b"zyab"];
+        let u_vec = [b"1x1x", b"x11a", b"ab11", b"zyab", b" 123", b"12/4", b"1.34", b"+123"];
         for u in u_vec.iter() {
             let chunk = le_bytes_to_u32(*u);
             assert_eq!(check_decimal_bit_u32(chunk), check_decimal(*u));
@@ -252,13 +294,13 @@ mod tests {
             assert_eq!(check_decimal_bit_u64(chunk), check_decimal(u));
         }
 
-        let u_vec = [b"1x1x1x1x", b"x11a11ax", b"ab11ab11", b"yabzyabz"];
+        let u_vec = [b"1x1x1x1x", b"x11a11ax", b"ab11ab11", b"yabzyabz", b"1234 678", b"/2345678", b"1234567\0", b"-1234567"];
         for u in u_vec.iter() {
             let chunk = le_bytes_to_u64(*u);
             assert_eq!(check_decimal_bit_u64(chunk), check_decimal(*u));
         }
 
-        let test_vec = vec![
+        let test_vec = [
             b"12345678",
             b"8765b321",
             b"zyxwvuts",
@@ -280,13 +322,71 @@ mod tests {
             assert_eq!(check_decimal_bit_u128(chunk), check_decimal(u));
         }
 
-        let u_vec = [b"1x1x1x1x1x1x1x1x", b"11111a11a1x1x1x1", b"ab11ab11a1x1x1x1", b"zyabzyabz1x1x1x1"];
+        let u_vec = [b"1x1x1x1x1x1x1x1x", b"11111a11a1x1x1x1", b"ab11ab11a1x1x1x1", b"zyabzyabz1x1x1x1", b"123456789012345 ", b"1234567,90123456"];
         for u in u_vec.iter() {
             let chunk = le_bytes_to_u128(*u);
             assert_eq!(check_decimal_bit_u128(chunk), check_decimal(*u));
         }
     }
 
+    // a byte of 0xFA or more carries into the next one when 6 is added
+    #[test]
+    fn test_check_decimal_adjacent_bytes() {
+        for pos in 0..15 {
+            for pair in 0..=u16::MAX {
+                let mut u = *b"0123456789012345";
+                u[pos..pos + 2].copy_from_slice(&pair.to_le_bytes());
+                let expected = check_decimal(&u);
+                assert_eq!(check_decimal_bit_u128(u128::from_le_bytes(u)), expected);
+                if pos < 7 {
+                    assert_eq!(check_decimal_bit_u64(u64::from_le_bytes(u[..8].try_into().unwrap())), expected);
+                }
+                if pos < 3 {
+                    assert_eq!(check_decimal_bit_u32(u32::from_le_bytes(u[..4].try_into().unwrap())), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_decimal_mask_u32() {
+        for byte in 0..=255u8 {
+            for pos in 0..4 {
+                let mut u = *b"0123";
+                u[pos] = byte;
+                let mask = non_decimal_mask_u32(u32::from_le_bytes(u));
+                let expected = if byte.is_ascii_digit() { 0 } else { 1u32 << (pos * 8) };
+                assert_eq!(mask, expected, "byte {:#x} at {}", byte, pos);
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_decimal_mask_u128() {
+        for byte in 0..=255u8 {
+            for pos in 0..16 {
+                let mut u = *b"0123456789012345";
+                u[pos] = byte;
+                let mask = non_decimal_mask_u128(u128::from_le_bytes(u));
+                let expected = if byte.is_ascii_digit() { 0 } else { 1u128 << (pos * 8) };
+                assert_eq!(mask, expected, "byte {:#x} at {}", byte, pos);
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_decimal_mask_u64() {
+        for byte in 0..=255u8 {
+            for pos in 0..8 {
+                let mut u = *b"01234567";
+                u[pos] = byte;
+                let mask = non_decimal_mask_u64(u64::from_le_bytes(u));
+                let expected = if byte.is_ascii_digit() { 0 } else { 1u64 << (pos * 8) };
+                assert_eq!(mask, expected, "byte {:#x} at {}", byte, pos);
+            }
+        }
+    }
+
     #[test]
     fn test_two_to_u16() {
         let u = b"12";
@@ -0,0 +1,60 @@
+//! `NonZero*` parsing on top of the primitive impls, zero is reported as [`ParseIntErr::Zero`]
+//!
+//! ```rust
+//! use biscuit_converter::{Biscuit, error::ParseIntErr};
+//! use std::num::NonZeroU64;
+//!
+//! assert_eq!(NonZeroU64::parse_decimal(b"42"), Ok(NonZeroU64::new(42).unwrap()));
+//! assert_eq!(NonZeroU64::parse_decimal(b"0000"), Err(ParseIntErr::Zero));
+//! ```
+use crate::Biscuit;
+use crate::sealed::Sealed;
+use crate::error::ParseIntErr;
+use std::num::{
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+};
+
+macro_rules! impl_non_zero_biscuit {
+    ($($non_zero:ty => $t:ty),* $(,)?) => {
+        $(
+            impl Sealed for $non_zero {}
+
+            impl Biscuit for $non_zero {
+                const MAX_DIGITS: usize = <$t>::MAX_DIGITS;
+                const IS_SIGNED: bool = <$t>::IS_SIGNED;
+
+                #[inline]
+                fn parse_decimal_slice(u: &[u8]) -> Result<Self, ParseIntErr> {
+                    <$non_zero>::new(<$t>::parse_decimal_slice(u)?).ok_or(ParseIntErr::Zero)
+                }
+
+                #[inline]
+                fn try_parse_decimal_slice(u: &[u8]) -> Option<Self> {
+                    <$non_zero>::new(<$t>::try_parse_decimal_slice(u)?)
+                }
+            }
+        )*
+    };
+}
+
+impl_non_zero_biscuit!(
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroI128 => i128,
+);
@@ -0,0 +1,177 @@
+//! Parallel parsing of delimited buffers with rayon (feature `rayon`)
+//!
+//! The buffer is cut into chunks of roughly equal size, each chunk boundary is moved to
+//! just after the next delimiter so that no field is split, and the chunks are parsed
+//! on the rayon thread pool. Results are joined in input order.
+//!
+//! ```rust
+//! use biscuit_converter::parallel::par_parse_delimited;
+//! use biscuit_converter::error::ParseIntErr;
+//!
+//! assert_eq!(par_parse_delimited::<u32>(b"1\n22\n333\n", b'\n'), Ok(vec![1, 22, 333]));
+//!
+//! let err = par_parse_delimited::<u32>(b"1,2,x,4", b',').unwrap_err();
+//! assert_eq!((err.index, err.err), (2, ParseIntErr::NonDecimal));
+//! ```
+use crate::Biscuit;
+use crate::error::ParseIntErr;
+use rayon::prelude::*;
+use std::fmt;
+
+// smaller chunks are not worth a task
+const MIN_CHUNK_LEN: usize = 1 << 16;
+// chunks per thread, so that a slow chunk does not hold up the others
+const CHUNKS_PER_THREAD: usize = 4;
+
+/// First field that failed to parse
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DelimitedErr {
+    /// 0-based index of the field in the whole buffer
+    pub index: usize,
+    pub err: ParseIntErr,
+}
+
+impl fmt::Display for DelimitedErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at field {}", self.err, self.index)
+    }
+}
+
+impl std::error::Error for DelimitedErr {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.err)
+    }
+}
+
+/// Parses the `delim`-separated fields of `buf` in parallel
+///
+/// A single trailing delimiter is a terminator, not the start of an empty field, so
+/// newline-terminated files parse as expected. On failure, the error of the first
+/// failing field in the buffer is returned.
+pub fn par_parse_delimited<T: Biscuit + Send>(buf: &[u8], delim: u8) -> Result<Vec<T>, DelimitedErr> {
+    let chunks = rayon::current_num_threads() * CHUNKS_PER_THREAD;
+    let chunk_len = (buf.len() / chunks).max(MIN_CHUNK_LEN);
+    par_parse_chunks(buf, delim, chunk_len)
+}
+
+// start of every chunk, each one right after a delimiter, followed by buf.len()
+fn chunk_bounds(buf: &[u8], delim: u8, chunk_len: usize) -> Vec<usize> {
+    let mut bounds = vec![0];
+    let mut nominal = chunk_len;
+    while nominal < buf.len() {
+        let start = *bounds.last().unwrap();
+        let from = nominal.max(start);
+        match buf[from..].iter().position(|&b| b == delim) {
+            Some(offset) if from + offset + 1 < buf.len() => bounds.push(from + offset + 1),
+            _ => break,
+        }
+        nominal += chunk_len;
+    }
+    bounds.push(buf.len());
+    bounds
+}
+
+// parses until the first error, which is returned with its index in the chunk
+fn parse_chunk<T: Biscuit>(chunk: &[u8], delim: u8) -> Result<Vec<T>, (usize, ParseIntErr)> {
+    let chunk = match chunk {
+        [] => return Ok(Vec::new()),
+        [rest @ .., last] if *last == delim => rest,
+        _ => chunk,
+    };
+    let mut values = Vec::with_capacity(chunk.len() / 4 + 1);
+    for (i, field) in chunk.split(|&b| b == delim).enumerate() {
+        values.push(T::parse_decimal(field).map_err(|err| (i, err))?);
+    }
+    Ok(values)
+}
+
+fn par_parse_chunks<T: Biscuit + Send>(buf: &[u8], delim: u8, chunk_len: usize) -> Result<Vec<T>, DelimitedErr> {
+    let bounds = chunk_bounds(buf, delim, chunk_len);
+    let parsed: Vec<_> = bounds
+        .par_windows(2)
+        .map(|bound| parse_chunk::<T>(&buf[bound[0]..bound[1]], delim))
+        .collect();
+
+    // the chunks before the first failing one parsed completely, so their lengths give the offset
+    let mut offset = 0;
+    for chunk in parsed.iter() {
+        match chunk {
+            Ok(values) => offset += values.len(),
+            Err((index, err)) => return Err(DelimitedErr { index: offset + index, err: err.clone() }),
+        }
+    }
+
+    let mut values = Vec::with_capacity(offset);
+    for chunk in parsed {
+        values.extend(chunk.unwrap());
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequential<T: Biscuit>(buf: &[u8], delim: u8) -> Result<Vec<T>, DelimitedErr> {
+        parse_chunk(buf, delim).map_err(|(index, err)| DelimitedErr { index, err })
+    }
+
+    #[test]
+    fn test_chunk_bounds() {
+        let buf = b"12,345,6,78,9";
+        for chunk_len in 1..=buf.len() + 1 {
+            let bounds = chunk_bounds(buf, b',', chunk_len);
+            assert_eq!(bounds[0], 0);
+            assert_eq!(*bounds.last().unwrap(), buf.len());
+            for pair in bounds.windows(2) {
+                assert!(pair[0] < pair[1], "Failed for chunk length {}: {:?}", chunk_len, bounds);
+                if pair[0] > 0 {
+                    assert_eq!(buf[pair[0] - 1], b',');
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_against_sequential() {
+        let mut buf = Vec::new();
+        for i in 0..20_000i64 {
+            buf.extend_from_slice(((i - 10_000) * 1_000_003).to_string().as_bytes());
+            buf.push(b'\n');
+        }
+        let expected = sequential::<i64>(&buf, b'\n');
+        assert_eq!(expected.as_ref().map(Vec::len), Ok(20_000));
+        for chunk_len in [1, 7, 100, 4096, 1 << 20] {
+            assert_eq!(par_parse_chunks::<i64>(&buf, b'\n', chunk_len), expected, "Failed for chunk length {}", chunk_len);
+        }
+        assert_eq!(par_parse_delimited::<i64>(&buf, b'\n'), expected);
+    }
+
+    #[test]
+    fn test_first_error() {
+        let mut fields: Vec<String> = (0..10_000u32).map(|i| i.to_string()).collect();
+        fields[7_777] = "77a7".to_string();
+        fields[9_000] = "".to_string();
+        let buf = fields.join(",");
+        for chunk_len in [1, 13, 1_000, 1 << 20] {
+            assert_eq!(
+                par_parse_chunks::<u16>(buf.as_bytes(), b',', chunk_len),
+                Err(DelimitedErr { index: 7_777, err: ParseIntErr::NonDecimal }),
+                "Failed for chunk length {}", chunk_len,
+            );
+        }
+        assert_eq!(
+            par_parse_delimited::<u8>(buf.as_bytes(), b','),
+            Err(DelimitedErr { index: 256, err: ParseIntErr::Overflow }),
+        );
+    }
+
+    #[test]
+    fn test_edges() {
+        assert_eq!(par_parse_delimited::<u8>(b"", b'\n'), Ok(vec![]));
+        assert_eq!(par_parse_delimited::<u8>(b"\n", b'\n'), Err(DelimitedErr { index: 0, err: ParseIntErr::Empty }));
+        assert_eq!(par_parse_delimited::<u8>(b"1\n\n", b'\n'), Err(DelimitedErr { index: 1, err: ParseIntErr::Empty }));
+        assert_eq!(par_parse_delimited::<u8>(b"1\n2", b'\n'), Ok(vec![1, 2]));
+        assert_eq!(par_parse_chunks::<u8>(b"1\n\n", b'\n', 1), Err(DelimitedErr { index: 1, err: ParseIntErr::Empty }));
+    }
+}
@@ -0,0 +1,349 @@
+//! Integers separated by ASCII whitespace, read from a [`BufRead`]
+//!
+//! Tokens are parsed in place from the reader's internal buffer. A token cut by a buffer
+//! refill is assembled in a fixed-size carry buffer, where redundant leading zeros are
+//! folded as they arrive, so no token is ever allocated.
+//!
+//! ```rust
+//! use biscuit_converter::reader::DecimalReader;
+//! use biscuit_converter::error::ParseIntErr;
+//!
+//! let dump = "12 -7\n  42\nx9 5\n";
+//! let mut reader = DecimalReader::<_, i32>::new(dump.as_bytes());
+//! assert_eq!(reader.next().unwrap().unwrap(), 12);
+//! assert_eq!(reader.next().unwrap().unwrap(), -7);
+//! assert_eq!(reader.next().unwrap().unwrap(), 42);
+//!
+//! let err = reader.next().unwrap().unwrap_err();
+//! assert_eq!(err.parse_err(), Some(ParseIntErr::NonDecimal));
+//! assert_eq!(err.position(), Some((3, 1)));
+//!
+//! assert_eq!(reader.next().unwrap().unwrap(), 5);
+//! assert!(reader.next().is_none());
+//! ```
+use crate::Biscuit;
+use crate::error::ParseIntErr;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::marker::PhantomData;
+
+// a sign and the 78 digits of U256::MAX fit, longer tokens can not be valid
+const CARRY_CAPACITY: usize = 80;
+
+#[derive(Debug)]
+pub enum DecimalReadErr {
+    Io(io::Error),
+    /// `line` and `column` are 1-based and point at the first byte of the token
+    Parse {
+        err: ParseIntErr,
+        line: usize,
+        column: usize,
+    },
+}
+
+impl DecimalReadErr {
+    pub fn parse_err(&self) -> Option<ParseIntErr> {
+        match self {
+            DecimalReadErr::Parse { err, .. } => Some(err.clone()),
+            DecimalReadErr::Io(_) => None,
+        }
+    }
+
+    /// `(line, column)` of the token that failed to parse
+    pub fn position(&self) -> Option<(usize, usize)> {
+        match self {
+            DecimalReadErr::Parse { line, column, .. } => Some((*line, *column)),
+            DecimalReadErr::Io(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for DecimalReadErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecimalReadErr::Io(err) => write!(f, "io error: {}", err),
+            DecimalReadErr::Parse { err, line, column } => write!(f, "{} at line {}, column {}", err, line, column),
+        }
+    }
+}
+
+impl std::error::Error for DecimalReadErr {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DecimalReadErr::Io(err) => Some(err),
+            DecimalReadErr::Parse { err, .. } => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for DecimalReadErr {
+    fn from(err: io::Error) -> Self {
+        DecimalReadErr::Io(err)
+    }
+}
+
+// a token cut by a buffer refill
+struct Carry {
+    bytes: [u8; CARRY_CAPACITY],
+    len: usize,
+    // the token did not fit
+    overflow: bool,
+}
+
+impl Carry {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len == 0 && !self.overflow
+    }
+
+    fn push(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            let sign = (self.len > 0 && self.bytes[0] == b'-') as usize;
+            // "0" or "-0" followed by a digit, the zero does not change the value
+            if byte.is_ascii_digit() && self.len == sign + 1 && self.bytes[sign] == b'0' {
+                self.bytes[sign] = byte;
+            } else if self.len < CARRY_CAPACITY {
+                self.bytes[self.len] = byte;
+                self.len += 1;
+            } else {
+                self.overflow = true;
+            }
+        }
+    }
+
+    fn take<T: Biscuit>(&mut self) -> Result<T, ParseIntErr> {
+        let token = &self.bytes[..self.len];
+        // as in parse_decimal, an input longer than MAX_DIGITS after its leading zeros is an overflow
+        let res = if self.overflow {
+            if T::IS_SIGNED && token[0] == b'-' {
+                Err(ParseIntErr::NegOverflow)
+            } else {
+                Err(ParseIntErr::Overflow)
+            }
+        } else {
+            T::parse_decimal(token)
+        };
+        self.len = 0;
+        self.overflow = false;
+        res
+    }
+}
+
+/// Iterator over the whitespace-separated integers of a reader
+///
+/// A token that fails to parse yields an error and the iteration goes on with the next
+/// token. An I/O error other than `Interrupted` is yielded once and ends the iteration.
+pub struct DecimalReader<R, T> {
+    reader: R,
+    line: usize,
+    column: usize,
+    // position of the token being read
+    token_line: usize,
+    token_column: usize,
+    in_token: bool,
+    carry: Carry,
+    done: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<R: BufRead, T: Biscuit> DecimalReader<R, T> {
+    pub fn new(reader: R) -> Self {
+        DecimalReader {
+            reader,
+            line: 1,
+            column: 1,
+            token_line: 1,
+            token_column: 1,
+            in_token: false,
+            carry: Carry { bytes: [0; CARRY_CAPACITY], len: 0, overflow: false },
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// 1-based position of the next byte to be read
+    #[inline]
+    pub fn position(&self) -> (usize, usize) {
+        (self.line, self.column)
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    #[inline]
+    fn located(&self, res: Result<T, ParseIntErr>) -> Result<T, DecimalReadErr> {
+        res.map_err(|err| DecimalReadErr::Parse { err, line: self.token_line, column: self.token_column })
+    }
+}
+
+impl<R: BufRead, T: Biscuit> Iterator for DecimalReader<R, T> {
+    type Item = Result<T, DecimalReadErr>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        loop {
+            let buf = match self.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err.into()));
+                },
+            };
+
+            if buf.is_empty() {
+                self.done = true;
+                if self.in_token {
+                    self.in_token = false;
+                    let res = self.carry.take();
+                    return Some(self.located(res));
+                }
+                return None;
+            }
+
+            let mut start = 0;
+            if !self.in_token {
+                while start < buf.len() && buf[start].is_ascii_whitespace() {
+                    if buf[start] == b'\n' {
+                        self.line += 1;
+                        self.column = 1;
+                    } else {
+                        self.column += 1;
+                    }
+                    start += 1;
+                }
+                if start == buf.len() {
+                    self.reader.consume(start);
+                    continue;
+                }
+                self.in_token = true;
+                self.token_line = self.line;
+                self.token_column = self.column;
+            }
+
+            match buf[start..].iter().position(u8::is_ascii_whitespace) {
+                Some(length) => {
+                    let token = &buf[start..start + length];
+                    let res = if self.carry.is_empty() {
+                        T::parse_decimal(token)
+                    } else {
+                        self.carry.push(token);
+                        self.carry.take()
+                    };
+                    self.column += length;
+                    self.in_token = false;
+                    self.reader.consume(start + length);
+                    return Some(self.located(res));
+                },
+                None => {
+                    let length = buf.len() - start;
+                    self.carry.push(&buf[start..]);
+                    self.column += length;
+                    self.reader.consume(start + length);
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufReader, Read};
+
+    fn read_all<T: Biscuit>(input: &[u8], capacity: usize) -> Vec<Result<T, (ParseIntErr, usize, usize)>> {
+        DecimalReader::<_, T>::new(BufReader::with_capacity(capacity, input))
+            .map(|res| res.map_err(|err| {
+                let (line, column) = err.position().unwrap();
+                (err.parse_err().unwrap(), line, column)
+            }))
+            .collect()
+    }
+
+    #[test]
+    fn test_tokens_across_refills() {
+        let mut input = String::new();
+        let mut expected = Vec::new();
+        for i in 0..500i64 {
+            let x = (i - 250) * 7_919_113_199 + i;
+            input.push_str(&x.to_string());
+            input.push_str(if i % 7 == 0 { "\n" } else if i % 3 == 0 { " \t " } else { " " });
+            expected.push(Ok(x));
+        }
+        for capacity in [1, 2, 3, 7, 16, 4096] {
+            assert_eq!(read_all::<i64>(input.as_bytes(), capacity), expected, "Failed for capacity {}", capacity);
+        }
+    }
+
+    #[test]
+    fn test_long_tokens() {
+        let zeros = "0".repeat(300);
+        let input = format!("{zeros}42 -{zeros}7 {zeros} {} -{}\n", "9".repeat(100), "1".repeat(90));
+        for capacity in [3, 1024] {
+            assert_eq!(
+                read_all::<i32>(input.as_bytes(), capacity),
+                vec![
+                    Ok(42),
+                    Ok(-7),
+                    Ok(0),
+                    Err((ParseIntErr::Overflow, 1, 908)),
+                    Err((ParseIntErr::NegOverflow, 1, 1009)),
+                ],
+                "Failed for capacity {}", capacity,
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_position() {
+        let input = b"1 2\n\n  3a 4\r\n-\n 99999";
+        for capacity in [1, 4, 64] {
+            assert_eq!(
+                read_all::<u16>(input, capacity),
+                vec![
+                    Ok(1),
+                    Ok(2),
+                    Err((ParseIntErr::NonDecimal, 3, 3)),
+                    Ok(4),
+                    Err((ParseIntErr::NonDecimal, 4, 1)),
+                    Err((ParseIntErr::Overflow, 5, 2)),
+                ],
+                "Failed for capacity {}", capacity,
+            );
+        }
+    }
+
+    struct FailingReader {
+        data: &'static [u8],
+        interrupted: bool,
+    }
+
+    impl Read for FailingReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if !self.interrupted {
+                self.interrupted = true;
+                return Err(io::Error::from(io::ErrorKind::Interrupted));
+            }
+            if self.data.is_empty() {
+                return Err(io::Error::other("broken pipe"));
+            }
+            let n = buf.len().min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_io_error() {
+        let reader = BufReader::with_capacity(4, FailingReader { data: b"12 34 5", interrupted: false });
+        let mut reader = DecimalReader::<_, u8>::new(reader);
+        assert_eq!(reader.next().unwrap().unwrap(), 12);
+        assert_eq!(reader.next().unwrap().unwrap(), 34);
+        assert!(matches!(reader.next(), Some(Err(DecimalReadErr::Io(_)))));
+        assert!(reader.next().is_none());
+    }
+}
@@ -0,0 +1,67 @@
+//! Conversions between integers and their sign and magnitude
+use crate::Biscuit;
+use crate::error::ParseIntErr;
+
+/// Integers built from or split into a sign and a `u128` magnitude
+///
+/// This is the bound of the digit encodings such as [`bcd`](crate::bcd): they only produce a
+/// sign and a magnitude, the range checks and the errors (`Overflow`, `NegOverflow`, `NonDecimal`
+/// for a negative unsigned) are the same as [`Biscuit::parse_decimal`]. Sealed through [`Biscuit`].
+pub trait SignMagnitude: Biscuit + Copy {
+    /// value from its sign and magnitude, `None` standing for a magnitude above `u128::MAX`
+    #[doc(hidden)]
+    fn from_sign_magnitude(neg: bool, magnitude: Option<u128>) -> Result<Self, ParseIntErr>;
+
+    /// sign and magnitude of the value
+    #[doc(hidden)]
+    fn to_sign_magnitude(self) -> (bool, u128);
+}
+
+macro_rules! impl_sign_magnitude_unsigned {
+    ($($t:ty)*) => {
+        $(
+            impl SignMagnitude for $t {
+                #[inline]
+                fn from_sign_magnitude(neg: bool, magnitude: Option<u128>) -> Result<Self, ParseIntErr> {
+                    if neg {
+                        return Err(ParseIntErr::NonDecimal);
+                    }
+                    magnitude.and_then(|val| <$t>::try_from(val).ok()).ok_or(ParseIntErr::Overflow)
+                }
+
+                #[inline]
+                fn to_sign_magnitude(self) -> (bool, u128) {
+                    (false, self as u128)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_sign_magnitude_signed {
+    ($($t:ty)*) => {
+        $(
+            impl SignMagnitude for $t {
+                #[inline]
+                fn from_sign_magnitude(neg: bool, magnitude: Option<u128>) -> Result<Self, ParseIntErr> {
+                    if neg {
+                        magnitude
+                            .filter(|&val| val <= <$t>::MIN.unsigned_abs() as u128)
+                            .map(|val| (val as i128).wrapping_neg() as $t)
+                            .ok_or(ParseIntErr::NegOverflow)
+                    } else {
+                        magnitude.and_then(|val| <$t>::try_from(val).ok()).ok_or(ParseIntErr::Overflow)
+                    }
+                }
+
+                #[inline]
+                fn to_sign_magnitude(self) -> (bool, u128) {
+                    (self < 0, self.unsigned_abs() as u128)
+                }
+            }
+        )*
+    };
+}
+
+impl_sign_magnitude_unsigned!(u8 u16 u32 u64 u128);
+impl_sign_magnitude_signed!(i8 i16 i32 i64 i128);
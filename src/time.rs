@@ -0,0 +1,223 @@
+//! Time of day to nanoseconds since midnight
+//!
+//! `HHMMSS` is loaded in one word whose 16-bit lanes hold the hour, minute and second
+//! after the first step of [`eight_to_u64`]. The lanes are range-checked together and
+//! folded in base 60 with the mask tricks of [`eight_to_u64`]. The fraction digits take
+//! a second word.
+//!
+//! ```rust
+//! use biscuit_converter::time::{parse_hhmmss_millis, parse_hhmmss_micros, parse_hh_mm_ss_nanos};
+//! use biscuit_converter::error::TimeErr;
+//!
+//! assert_eq!(parse_hhmmss_millis(b"093000250"), Ok(34_200_250_000_000));
+//! assert_eq!(parse_hhmmss_micros(b"235959999999"), Ok(86_399_999_999_000));
+//! assert_eq!(parse_hh_mm_ss_nanos(b"00:00:01.000000001"), Ok(1_000_000_001));
+//! assert_eq!(parse_hhmmss_millis(b"240000000"), Err(TimeErr::Hour));
+//! assert_eq!(parse_hh_mm_ss_nanos(b"12:60:00.000000000"), Err(TimeErr::Minute));
+//! ```
+use crate::error::TimeErr;
+use crate::little_endian_decimal::{
+    check_decimal_bit_u64,
+    check_decimal_bit_u128,
+    eight_to_u64,
+    sixteen_to_u128,
+};
+use crate::utils::{load_u64, load_u128};
+
+pub const NANOS_PER_SECOND: u64 = 1_000_000_000;
+pub const NANOS_PER_DAY: u64 = 86_400 * NANOS_PER_SECOND;
+
+// b"00" in the top lane of a word holding "HHMMSS"
+const ZERO_PAD_TOP_LANE: u64 = 0x3030 << 48;
+// 0x8000 minus the limits [24, 60, 60, 1] of the lanes [HH, MM, SS, 00]
+const LANE_LIMIT_COMPLEMENT: u64 = 0x7FFF_7FC4_7FC4_7FE8;
+// same with the limit of SS at 61 for a leap second
+const LANE_LIMIT_COMPLEMENT_LEAP: u64 = 0x7FFF_7FC3_7FC4_7FE8;
+const LANE_SIGN_MASK: u64 = 0x8000_8000_8000_8000;
+// bytes 2 and 5 of "HH:MM:SS"
+const COLON_MASK: u64 = 0x0000_FF00_00FF_0000;
+const COLONS: u64 = 0x0000_3A00_003A_0000;
+
+/// "HHMMSS" in the low six bytes of the word, b"00" above
+#[inline]
+pub(crate) fn load_hhmmss(u: &[u8; 6]) -> u64 {
+    let low = u32::from_le_bytes(u[..4].try_into().unwrap()) as u64;
+    let high = u16::from_le_bytes(u[4..].try_into().unwrap()) as u64;
+    low | (high << 32) | ZERO_PAD_TOP_LANE
+}
+
+/// "HH:MM:SS" packed to the layout of [`load_hhmmss`]
+#[inline]
+pub(crate) fn load_hh_mm_ss(u: &[u8; 8]) -> Result<u64, TimeErr> {
+    let chunk = u64::from_le_bytes(*u);
+    if chunk & COLON_MASK != COLONS {
+        return Err(TimeErr::Separator);
+    }
+    Ok((chunk & 0xFFFF) | ((chunk >> 8) & 0xFFFF_0000) | ((chunk >> 16) & 0xFFFF_0000_0000) | ZERO_PAD_TOP_LANE)
+}
+
+/// Seconds of the day from a word built by [`load_hhmmss`]
+#[inline]
+pub(crate) fn hhmmss_to_seconds(chunk: u64) -> Result<u32, TimeErr> {
+    lanes_to_seconds(chunk, LANE_LIMIT_COMPLEMENT)
+}
+
+/// [`hhmmss_to_seconds`] also taking second 60, which lands on the next second
+/// (`23:59:60` gives 86400)
+#[inline]
+pub(crate) fn hhmmss_to_seconds_leap(chunk: u64) -> Result<u32, TimeErr> {
+    lanes_to_seconds(chunk, LANE_LIMIT_COMPLEMENT_LEAP)
+}
+
+#[inline(always)]
+fn lanes_to_seconds(mut chunk: u64, limit_complement: u64) -> Result<u32, TimeErr> {
+    if !check_decimal_bit_u64(chunk) {
+        return Err(TimeErr::NonDecimal);
+    }
+    // 1-byte mask trick, 16-bit lanes [HH, MM, SS, 00]
+    let lower_digits = (chunk & 0x0f000f000f000f00) >> 8;
+    let upper_digits = (chunk & 0x000f000f000f000f) * 10;
+    chunk = lower_digits + upper_digits;
+
+    // a lane at or above its limit sets its sign bit
+    let out_of_range = (chunk + limit_complement) & LANE_SIGN_MASK;
+    if out_of_range != 0 {
+        return Err(match out_of_range.trailing_zeros() / 16 {
+            0 => TimeErr::Hour,
+            1 => TimeErr::Minute,
+            _ => TimeErr::Second,
+        });
+    }
+
+    // 2-byte mask trick in base 60, lanes [HH * 60 + MM, SS]
+    let lower_digits = (chunk & 0x0000_0000_00ff_0000) >> 16;
+    let upper_digits = (chunk & 0x0000_0000_0000_00ff) * 60;
+    chunk = (chunk & 0x0000_00ff_0000_0000) + lower_digits + upper_digits;
+
+    // 4-byte mask trick in base 60
+    let lower_digits = (chunk & 0x0000_ffff_0000_0000) >> 32;
+    let upper_digits = (chunk & 0x0000_0000_ffff_ffff) * 60;
+    Ok((lower_digits + upper_digits) as u32)
+}
+
+/// 1 to 9 fraction digits to nanoseconds
+#[inline]
+pub(crate) fn fraction_to_nanos(u: &[u8]) -> Result<u32, TimeErr> {
+    debug_assert!((1..=9).contains(&u.len()));
+    let value = if u.len() <= 8 {
+        let chunk = load_u64(u);
+        if !check_decimal_bit_u64(chunk) {
+            return Err(TimeErr::NonDecimal);
+        }
+        eight_to_u64(chunk) as u32
+    } else {
+        let chunk = load_u128(u);
+        if !check_decimal_bit_u128(chunk) {
+            return Err(TimeErr::NonDecimal);
+        }
+        sixteen_to_u128(chunk) as u32
+    };
+    Ok(value * 10u32.pow(9 - u.len() as u32))
+}
+
+#[inline]
+fn hhmmss_fraction(u: &[u8]) -> Result<u64, TimeErr> {
+    let seconds = hhmmss_to_seconds(load_hhmmss(u[..6].try_into().unwrap()))?;
+    Ok(seconds as u64 * NANOS_PER_SECOND + fraction_to_nanos(&u[6..])? as u64)
+}
+
+/// `HHMMSSmmm` to nanoseconds since midnight
+pub fn parse_hhmmss_millis(u: &[u8]) -> Result<u64, TimeErr> {
+    if u.len() != 9 {
+        return Err(TimeErr::Length);
+    }
+    hhmmss_fraction(u)
+}
+
+/// `HHMMSSuuuuuu` to nanoseconds since midnight
+pub fn parse_hhmmss_micros(u: &[u8]) -> Result<u64, TimeErr> {
+    if u.len() != 12 {
+        return Err(TimeErr::Length);
+    }
+    hhmmss_fraction(u)
+}
+
+/// `HH:MM:SS.nnnnnnnnn` to nanoseconds since midnight
+pub fn parse_hh_mm_ss_nanos(u: &[u8]) -> Result<u64, TimeErr> {
+    let u: &[u8; 18] = u.try_into().map_err(|_| TimeErr::Length)?;
+    if u[8] != b'.' {
+        return Err(TimeErr::Separator);
+    }
+    let seconds = hhmmss_to_seconds(load_hh_mm_ss(u[..8].try_into().unwrap())?)?;
+    Ok(seconds as u64 * NANOS_PER_SECOND + fraction_to_nanos(&u[9..])? as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_second() {
+        for second in 0..86_400u64 {
+            let (h, m, s) = (second / 3600, second / 60 % 60, second % 60);
+            let nanos = second * NANOS_PER_SECOND;
+            let millis = format!("{:02}{:02}{:02}{:03}", h, m, s, second % 1000);
+            assert_eq!(parse_hhmmss_millis(millis.as_bytes()), Ok(nanos + second % 1000 * 1_000_000), "Failed for {}", millis);
+            let micros = format!("{:02}{:02}{:02}{:06}", h, m, s, second * 11);
+            assert_eq!(parse_hhmmss_micros(micros.as_bytes()), Ok(nanos + second * 11_000), "Failed for {}", micros);
+            let full = format!("{:02}:{:02}:{:02}.{:09}", h, m, s, second * 11_573);
+            assert_eq!(parse_hh_mm_ss_nanos(full.as_bytes()), Ok(nanos + second * 11_573), "Failed for {}", full);
+        }
+        assert_eq!(parse_hh_mm_ss_nanos(b"23:59:59.999999999"), Ok(NANOS_PER_DAY - 1));
+    }
+
+    #[test]
+    fn test_ranges() {
+        for x in 24..100 {
+            assert_eq!(parse_hhmmss_millis(format!("{}0000000", x).as_bytes()), Err(TimeErr::Hour));
+        }
+        for x in 60..100 {
+            assert_eq!(parse_hhmmss_millis(format!("23{}00000", x).as_bytes()), Err(TimeErr::Minute));
+            assert_eq!(parse_hhmmss_micros(format!("2359{}000000", x).as_bytes()), Err(TimeErr::Second));
+            assert_eq!(parse_hh_mm_ss_nanos(format!("00:00:{}.000000000", x).as_bytes()), Err(TimeErr::Second));
+        }
+        // the first failing lane is reported
+        assert_eq!(parse_hhmmss_millis(b"996099000"), Err(TimeErr::Hour));
+    }
+
+    #[test]
+    fn test_malformed() {
+        let valid = b"12:34:56.123456789";
+        for i in 0..valid.len() {
+            for b in [b'/', b':', b'.', b' ', b'a', 0] {
+                let mut u = *valid;
+                if u[i] == b {
+                    continue;
+                }
+                u[i] = b;
+                let expected = match i {
+                    2 | 5 | 8 => TimeErr::Separator,
+                    _ => TimeErr::NonDecimal,
+                };
+                assert_eq!(parse_hh_mm_ss_nanos(&u), Err(expected), "Failed for {:?}", u);
+            }
+        }
+        for i in 0..12 {
+            let mut u = *b"123456123456";
+            u[i] = b'/';
+            assert_eq!(parse_hhmmss_micros(&u), Err(TimeErr::NonDecimal));
+        }
+        assert_eq!(parse_hhmmss_millis(b"12345612"), Err(TimeErr::Length));
+        assert_eq!(parse_hhmmss_micros(b"123456123"), Err(TimeErr::Length));
+        assert_eq!(parse_hh_mm_ss_nanos(b"12:34:56.123"), Err(TimeErr::Length));
+        assert_eq!(parse_hh_mm_ss_nanos(b"12:34:56,123456789"), Err(TimeErr::Separator));
+    }
+
+    #[test]
+    fn test_fraction() {
+        assert_eq!(fraction_to_nanos(b"1"), Ok(100_000_000));
+        assert_eq!(fraction_to_nanos(b"12345678"), Ok(123_456_780));
+        assert_eq!(fraction_to_nanos(b"000000009"), Ok(9));
+        assert_eq!(fraction_to_nanos(b"00000000a"), Err(TimeErr::NonDecimal));
+    }
+}
@@ -0,0 +1,271 @@
+//! Full timestamps to nanoseconds since the Unix epoch
+//!
+//! The date and the time of day go through the SWAR paths of [`date`](crate::date) and
+//! [`time`](crate::time). The result is an `i64`, so timestamps before 1677-09-21 or
+//! after 2262-04-11 are reported as [`TimeErr::OutOfRange`].
+//!
+//! ```rust
+//! use biscuit_converter::timestamp::parse_rfc3339;
+//! use biscuit_converter::error::TimeErr;
+//!
+//! assert_eq!(parse_rfc3339(b"2026-10-18T09:30:00.123456789+09:00"), Ok(1_792_283_400_123_456_789));
+//! assert_eq!(parse_rfc3339(b"1970-01-01T00:00:00Z"), Ok(0));
+//! assert_eq!(parse_rfc3339(b"2026-02-29T00:00:00Z"), Err(TimeErr::Day));
+//! assert_eq!(parse_rfc3339(b"2026-10-18T09:30:00+24:00"), Err(TimeErr::Offset));
+//! ```
+//!
+//! FIX UTCTimestamp (`YYYYMMDD-HH:MM:SS[.sss]`) is parsed and formatted by
+//! [`parse_utc_timestamp`] and [`format_utc_timestamp`].
+//!
+//! ```rust
+//! use biscuit_converter::timestamp::{parse_utc_timestamp, format_utc_timestamp, Precision, UTC_TIMESTAMP_MAX_LEN};
+//!
+//! let nanos = parse_utc_timestamp(b"20090107-18:15:16.250").unwrap();
+//! assert_eq!(nanos, 1_231_352_116_250_000_000);
+//!
+//! let mut buf = [0u8; UTC_TIMESTAMP_MAX_LEN];
+//! assert_eq!(format_utc_timestamp(nanos, Precision::Micros, &mut buf), b"20090107-18:15:16.250000");
+//! ```
+use crate::date::{civil_from_days, load_iso_date, yyyymmdd_to_days};
+use crate::error::TimeErr;
+use crate::format::write_u64;
+use crate::kernels::checked_two_to_u16;
+use crate::time::{fraction_to_nanos, hhmmss_to_seconds_leap, load_hh_mm_ss, NANOS_PER_SECOND};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// length of a UTCTimestamp with nanoseconds
+pub const UTC_TIMESTAMP_MAX_LEN: usize = 27;
+const UTC_TIMESTAMP_SECONDS_LEN: usize = 17;
+
+/// Fraction digits written by [`format_utc_timestamp`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    Seconds,
+    Millis,
+    Micros,
+    Nanos,
+}
+
+impl Precision {
+    #[inline]
+    pub fn fraction_digits(self) -> usize {
+        match self {
+            Precision::Seconds => 0,
+            Precision::Millis => 3,
+            Precision::Micros => 6,
+            Precision::Nanos => 9,
+        }
+    }
+}
+
+/// Nanoseconds since the epoch, checked against the range of `i64`
+#[inline]
+pub(crate) fn unix_nanos(days: i32, seconds: i64, nanos: u32) -> Result<i64, TimeErr> {
+    let seconds = days as i64 * SECONDS_PER_DAY + seconds;
+    // before the epoch the fraction is taken from the next second, which keeps i64::MIN in range
+    let (seconds, nanos) = if seconds < 0 && nanos > 0 {
+        (seconds + 1, nanos as i64 - NANOS_PER_SECOND as i64)
+    } else {
+        (seconds, nanos as i64)
+    };
+    seconds
+        .checked_mul(NANOS_PER_SECOND as i64)
+        .and_then(|val| val.checked_add(nanos))
+        .ok_or(TimeErr::OutOfRange)
+}
+
+/// `Z`, `z` or `±HH:MM` at the end of `u` to its length and its offset in seconds
+#[inline]
+fn utc_offset(u: &[u8]) -> Result<(usize, i64), TimeErr> {
+    let offset = match u {
+        [.., b'Z' | b'z'] => return Ok((1, 0)),
+        [.., sign @ (b'+' | b'-'), h0, h1, b':', m0, m1] => {
+            let hour = checked_two_to_u16(&[*h0, *h1]).map_err(|_| TimeErr::Offset)?;
+            let minute = checked_two_to_u16(&[*m0, *m1]).map_err(|_| TimeErr::Offset)?;
+            if hour > 23 || minute > 59 {
+                return Err(TimeErr::Offset);
+            }
+            let offset = (hour as i64 * 60 + minute as i64) * 60;
+            if *sign == b'-' { -offset } else { offset }
+        },
+        _ => return Err(TimeErr::Offset),
+    };
+    Ok((6, offset))
+}
+
+/// RFC 3339 timestamp to nanoseconds since the Unix epoch
+///
+/// The layout is `YYYY-MM-DDTHH:MM:SS[.f]` followed by `Z` or `±HH:MM`, with 1 to 9
+/// fraction digits. `T` may also be `t` or a space, and `Z` may be `z`. A leap second
+/// (second 60) is folded into the next second, as chrono does, so `23:59:60Z` equals
+/// `00:00:00Z` of the next day.
+pub fn parse_rfc3339(u: &[u8]) -> Result<i64, TimeErr> {
+    if u.len() < 19 {
+        return Err(TimeErr::Length);
+    }
+    let days = yyyymmdd_to_days(load_iso_date(u[..10].try_into().unwrap())?)?;
+    if !matches!(u[10], b'T' | b't' | b' ') {
+        return Err(TimeErr::Separator);
+    }
+    let seconds = hhmmss_to_seconds_leap(load_hh_mm_ss(u[11..19].try_into().unwrap())?)?;
+
+    let rest = &u[19..];
+    let (offset_len, offset) = utc_offset(rest)?;
+    let nanos = match &rest[..rest.len() - offset_len] {
+        [] => 0,
+        [b'.', fraction @ ..] if (1..=9).contains(&fraction.len()) => fraction_to_nanos(fraction)?,
+        [b'.', ..] => return Err(TimeErr::Fraction),
+        _ => return Err(TimeErr::Separator),
+    };
+    unix_nanos(days, seconds as i64 - offset, nanos)
+}
+
+/// FIX UTCTimestamp to nanoseconds since the Unix epoch
+///
+/// The layout is `YYYYMMDD-HH:MM:SS` with an optional fraction of 3, 6 or 9 digits.
+/// A leap second (second 60) is folded into the next second, as in [`parse_rfc3339`].
+pub fn parse_utc_timestamp(u: &[u8]) -> Result<i64, TimeErr> {
+    if u.len() < UTC_TIMESTAMP_SECONDS_LEN {
+        return Err(TimeErr::Length);
+    }
+    let days = yyyymmdd_to_days(u64::from_le_bytes(u[..8].try_into().unwrap()))?;
+    if u[8] != b'-' {
+        return Err(TimeErr::Separator);
+    }
+    let seconds = hhmmss_to_seconds_leap(load_hh_mm_ss(u[9..17].try_into().unwrap())?)?;
+    let nanos = match &u[17..] {
+        [] => 0,
+        [b'.', fraction @ ..] if matches!(fraction.len(), 3 | 6 | 9) => fraction_to_nanos(fraction)?,
+        [b'.', ..] => return Err(TimeErr::Fraction),
+        _ => return Err(TimeErr::Length),
+    };
+    unix_nanos(days, seconds as i64, nanos)
+}
+
+/// Writes `nanos` since the Unix epoch as a FIX UTCTimestamp at the start of `buf` and
+/// returns the written bytes, digits below `precision` are truncated
+pub fn format_utc_timestamp(nanos: i64, precision: Precision, buf: &mut [u8; UTC_TIMESTAMP_MAX_LEN]) -> &[u8] {
+    let seconds = nanos.div_euclid(NANOS_PER_SECOND as i64);
+    let fraction = nanos.rem_euclid(NANOS_PER_SECOND as i64) as u64;
+    let (year, month, day) = civil_from_days(seconds.div_euclid(SECONDS_PER_DAY) as i32);
+    let second_of_day = seconds.rem_euclid(SECONDS_PER_DAY) as u64;
+
+    // the range of i64 nanoseconds keeps the year in four digits
+    buf[..UTC_TIMESTAMP_SECONDS_LEN].copy_from_slice(b"00000000-00:00:00");
+    write_u64(year as u64 * 10_000 + month as u64 * 100 + day as u64, buf, 8);
+    write_u64(second_of_day / 3_600, buf, 11);
+    write_u64(second_of_day / 60 % 60, buf, 14);
+    write_u64(second_of_day % 60, buf, 17);
+
+    let digits = precision.fraction_digits();
+    if digits == 0 {
+        return &buf[..UTC_TIMESTAMP_SECONDS_LEN];
+    }
+    let end = UTC_TIMESTAMP_SECONDS_LEN + 1 + digits;
+    buf[UTC_TIMESTAMP_SECONDS_LEN] = b'.';
+    buf[UTC_TIMESTAMP_SECONDS_LEN + 1..end].fill(b'0');
+    write_u64(fraction / 10u64.pow(9 - digits as u32), buf, end);
+    &buf[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fraction_and_offset() {
+        let base = 1_792_315_800i64 * 1_000_000_000;
+        let cases: [(&[u8], i64); 9] = [
+            (b"2026-10-18T09:30:00Z", base),
+            (b"2026-10-18t09:30:00z", base),
+            (b"2026-10-18 09:30:00.1Z", base + 100_000_000),
+            (b"2026-10-18T09:30:00.12345678Z", base + 123_456_780),
+            (b"2026-10-18T09:30:00.000000001+00:00", base + 1),
+            (b"2026-10-18T09:30:00-00:00", base),
+            (b"2026-10-18T09:30:00+09:00", base - 9 * 3_600_000_000_000),
+            (b"2026-10-18T09:30:00.5-05:30", base + 19_800_000_000_000 + 500_000_000),
+            (b"2026-10-18T00:00:00+23:59", base - (9 * 3_600 + 30 * 60 + 23 * 3_600 + 59 * 60) * 1_000_000_000),
+        ];
+        for (u, expected) in cases {
+            assert_eq!(parse_rfc3339(u), Ok(expected), "Failed for {}", String::from_utf8_lossy(u));
+        }
+    }
+
+    #[test]
+    fn test_range() {
+        assert_eq!(parse_rfc3339(b"1969-12-31T23:59:59.999999999Z"), Ok(-1));
+        assert_eq!(parse_rfc3339(b"2262-04-11T23:47:16.854775807Z"), Ok(i64::MAX));
+        assert_eq!(parse_rfc3339(b"2262-04-11T23:47:16.854775808Z"), Err(TimeErr::OutOfRange));
+        assert_eq!(parse_rfc3339(b"1677-09-21T00:12:43.145224192Z"), Ok(i64::MIN));
+        assert_eq!(parse_rfc3339(b"1677-09-21T00:12:43.145224191Z"), Err(TimeErr::OutOfRange));
+        assert_eq!(parse_rfc3339(b"0000-01-01T00:00:00Z"), Err(TimeErr::OutOfRange));
+    }
+
+    #[test]
+    fn test_leap_second() {
+        let cases: [(&[u8], &[u8]); 4] = [
+            (b"2016-12-31T23:59:60Z", b"2017-01-01T00:00:00Z"),
+            (b"2016-12-31T23:59:60.5Z", b"2017-01-01T00:00:00.5Z"),
+            (b"2016-12-31T15:59:60-08:00", b"2017-01-01T00:00:00Z"),
+            (b"2015-06-30T23:59:60.999999999Z", b"2015-07-01T00:00:00.999999999Z"),
+        ];
+        for (leap, next) in cases {
+            assert_eq!(parse_rfc3339(leap), parse_rfc3339(next), "Failed for {}", String::from_utf8_lossy(leap));
+            assert!(parse_rfc3339(leap).is_ok());
+        }
+        assert_eq!(parse_rfc3339(b"2262-04-11T23:47:60Z"), Err(TimeErr::OutOfRange));
+    }
+
+    #[test]
+    fn test_errors() {
+        let cases: [(&[u8], TimeErr); 16] = [
+            (b"2026-10-18T09:30Z", TimeErr::Length),
+            (b"2026-00-18T09:30:00Z", TimeErr::Month),
+            (b"2026-04-31T09:30:00Z", TimeErr::Day),
+            (b"2026-10-18T24:00:00Z", TimeErr::Hour),
+            (b"2026-10-18T09:60:00Z", TimeErr::Minute),
+            (b"2026-10-18T09:30:61Z", TimeErr::Second),
+            (b"2026-10-18X09:30:00Z", TimeErr::Separator),
+            (b"2026/10/18T09:30:00Z", TimeErr::Separator),
+            (b"2026-10-18T09.30.00Z", TimeErr::Separator),
+            (b"2026-10-18T09:30:00", TimeErr::Offset),
+            (b"2026-10-18T09:30:00+0900", TimeErr::Offset),
+            (b"2026-10-18T09:30:00+09:60", TimeErr::Offset),
+            (b"2026-10-18T09:30:00.Z", TimeErr::Fraction),
+            (b"2026-10-18T09:30:00.1234567890Z", TimeErr::Fraction),
+            (b"2026-10-18T09:30:00.12a4Z", TimeErr::NonDecimal),
+            (b"2026-10-18T09:30:00,5Z", TimeErr::Separator),
+        ];
+        for (u, expected) in cases {
+            assert_eq!(parse_rfc3339(u), Err(expected), "Failed for {}", String::from_utf8_lossy(u));
+        }
+    }
+
+    #[test]
+    fn test_utc_timestamp() {
+        let mut buf = [0u8; UTC_TIMESTAMP_MAX_LEN];
+        for nanos in [0, -1, 1, i64::MIN, i64::MAX, 951_782_400_000_000_000, -86_400_000_000_001] {
+            let formatted = format_utc_timestamp(nanos, Precision::Nanos, &mut buf).to_vec();
+            assert_eq!(parse_utc_timestamp(&formatted), Ok(nanos), "Failed for {}", String::from_utf8_lossy(&formatted));
+        }
+        assert_eq!(format_utc_timestamp(-1, Precision::Millis, &mut buf), b"19691231-23:59:59.999");
+        assert_eq!(format_utc_timestamp(951_782_400_000_000_000, Precision::Seconds, &mut buf), b"20000229-00:00:00");
+
+        assert_eq!(parse_utc_timestamp(b"20161231-23:59:60"), parse_utc_timestamp(b"20170101-00:00:00"));
+        assert_eq!(parse_utc_timestamp(b"20161231-23:59:60.250"), Ok(1_483_228_800_250_000_000));
+
+        let cases: [(&[u8], TimeErr); 7] = [
+            (b"20090107-18:15", TimeErr::Length),
+            (b"20090107-18:15:16.2", TimeErr::Fraction),
+            (b"20090107-18:15:16.", TimeErr::Fraction),
+            (b"20090107-18:15:16Z", TimeErr::Length),
+            (b"20090107T18:15:16", TimeErr::Separator),
+            (b"20090132-18:15:16", TimeErr::Day),
+            (b"20090107-18:15:61", TimeErr::Second),
+        ];
+        for (u, expected) in cases {
+            assert_eq!(parse_utc_timestamp(u), Err(expected), "Failed for {}", String::from_utf8_lossy(u));
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::Biscuit;
+use crate::sealed::Sealed;
 use crate::error::ParseIntErr;
 
 use crate::little_endian_decimal::{
@@ -9,20 +10,195 @@ use crate::little_endian_decimal::{
     checked_conversion_u128,
 };
 
-// to handle integer converter overflow
-const I128_MAX_AS_U128: u128 = 170141183460469231731687303715884105727;
-const I128_MIN_ABS_AS_U128: u128 = 170141183460469231731687303715884105728;
-const I64_MAX_AS_U64: u64 = 9223372036854775807;
-const I64_MIN_ABS_AS_U64: u64 = 9223372036854775808;
-const I32_MAX_AS_U32: u32 = 2147483647;
-const I32_MIN_ABS_AS_U32: u32 = 2147483648;
-const I16_MAX_AS_U16: u16 = 32767;
-const I16_MIN_ABS_AS_U16: u16 = 32768;
-const I8_MAX_AS_U8: u8 = 127;
-const I8_MIN_ABS_AS_U8: u8 = 128;
+/// Unsigned integers the length-generic core accumulates into
+pub(crate) trait DecimalAccumulator: Copy + PartialOrd {
+    const ZERO: Self;
+    /// number of digits of `MAX`, the only length at which the accumulator can overflow
+    const MAX_DIGITS: usize;
 
-impl Biscuit for u128 {
-    fn unsinged_decimal_core(u: &[u8], neg_max_check: bool, pos_max_check: bool) -> Result<Self, crate::error::ParseIntErr> {
+    /// truncating cast, the chunks handed over always fit
+    fn from_chunk(chunk: u128) -> Self;
+    fn mul_add(self, scale: Self, chunk: Self) -> Self;
+    fn checked_mul_add(self, scale: Self, chunk: Self) -> Option<Self>;
+}
+
+macro_rules! impl_decimal_accumulator {
+    ($t:ty, $max_digits:literal) => {
+        impl DecimalAccumulator for $t {
+            const ZERO: Self = 0;
+            const MAX_DIGITS: usize = $max_digits;
+
+            #[inline(always)]
+            fn from_chunk(chunk: u128) -> Self {
+                chunk as $t
+            }
+
+            #[inline(always)]
+            fn mul_add(self, scale: Self, chunk: Self) -> Self {
+                self * scale + chunk
+            }
+
+            #[inline(always)]
+            fn checked_mul_add(self, scale: Self, chunk: Self) -> Option<Self> {
+                self.checked_mul(scale)?.checked_add(chunk)
+            }
+        }
+    };
+}
+
+impl_decimal_accumulator!(u8, 3);
+impl_decimal_accumulator!(u16, 5);
+impl_decimal_accumulator!(u32, 10);
+impl_decimal_accumulator!(u64, 20);
+impl_decimal_accumulator!(u128, 39);
+
+// appends a chunk of K digits, only the longest inputs can overflow
+#[inline(always)]
+fn push_chunk<T: DecimalAccumulator, const K: u32>(acc: T, chunk: u128, checked: bool) -> Result<T, ParseIntErr> {
+    let scale = T::from_chunk(const { 10u128.pow(K) });
+    let chunk = T::from_chunk(chunk);
+    if checked {
+        acc.checked_mul_add(scale, chunk).ok_or(ParseIntErr::Overflow)
+    } else {
+        Ok(acc.mul_add(scale, chunk))
+    }
+}
+
+/// Kernel for inputs of exactly N digits
+///
+/// The input is cut from the left into greedy chunks of 16, 8, 4, 2 and 1 digits,
+/// e.g., 39 digits are read as 16 + 16 + 4 + 2 + 1. N is a constant, so every
+/// length gets its own straight-line code. Only the last chunk of a MAX_DIGITS long
+/// input can overflow, every other push holds fewer digits than MAX_DIGITS.
+#[inline(always)]
+fn fixed_length<T: DecimalAccumulator, const N: usize>(u: &[u8]) -> Result<T, ParseIntErr> {
+    let u: &[u8; N] = u.try_into().unwrap();
+    let checked = N >= T::MAX_DIGITS;
+    let mut acc = T::ZERO;
+    let mut pos = 0;
+    while N - pos >= 16 {
+        acc = push_chunk::<T, 16>(acc, checked_conversion_u128(&u[pos..pos + 16])?, checked && pos + 16 == N)?;
+        pos += 16;
+    }
+    if N - pos >= 8 {
+        acc = push_chunk::<T, 8>(acc, checked_conversion_u64(&u[pos..pos + 8])? as u128, checked && pos + 8 == N)?;
+        pos += 8;
+    }
+    if N - pos >= 4 {
+        acc = push_chunk::<T, 4>(acc, checked_conversion_u32(&u[pos..pos + 4])? as u128, checked && pos + 4 == N)?;
+        pos += 4;
+    }
+    if N - pos >= 2 {
+        acc = push_chunk::<T, 2>(acc, checked_conversion_u16(&u[pos..pos + 2])? as u128, checked && pos + 2 == N)?;
+        pos += 2;
+    }
+    if N - pos >= 1 {
+        acc = push_chunk::<T, 1>(acc, checked_conversion_u8(&u[pos..])? as u128, checked)?;
+    }
+    Ok(acc)
+}
+
+// inputs longer than MAX_DIGITS can only be parsed when they have leading zeros,
+// the last digit is kept so that an all-zero input still reads as 0
+#[inline]
+fn strip_leading_zeros<T: DecimalAccumulator>(u: &[u8]) -> Result<&[u8], ParseIntErr> {
+    let mut start = 0;
+    while start < u.len() - 1 && u[start] == b'0' {
+        start += 1;
+    }
+    if u.len() - start > T::MAX_DIGITS {
+        return Err(ParseIntErr::Overflow);
+    }
+    Ok(&u[start..])
+}
+
+/// Unsigned parsing of digits without a sign
+pub(crate) trait UnsignedCore: Sized {
+    fn unsigned_decimal_core(u: &[u8]) -> Result<Self, ParseIntErr>;
+}
+
+macro_rules! impl_unsigned_biscuit {
+    ($t:ty, $signed:ty, [$($length:literal)*]) => {
+        impl UnsignedCore for $t {
+            fn unsigned_decimal_core(u: &[u8]) -> Result<Self, ParseIntErr> {
+                match u.len() {
+                    0 => Err(ParseIntErr::Empty),
+                    $($length => fixed_length::<$t, $length>(u),)*
+                    _ => {
+                        let u = strip_leading_zeros::<$t>(u)?;
+                        Self::unsigned_decimal_core(u)
+                    },
+                }
+            }
+        }
+
+        impl Sealed for $t {}
+
+        impl Biscuit for $t {
+            const MAX_DIGITS: usize = <$t as DecimalAccumulator>::MAX_DIGITS;
+            const IS_SIGNED: bool = false;
+
+            #[inline]
+            fn parse_decimal_slice(u: &[u8]) -> Result<Self, ParseIntErr> {
+                Self::unsigned_decimal_core(u)
+            }
+
+            #[inline]
+            fn parse_decimal_array<const N: usize>(u: &[u8; N]) -> Result<Self, ParseIntErr> {
+                if N == 0 || N > <$t as DecimalAccumulator>::MAX_DIGITS {
+                    Self::unsigned_decimal_core(u)
+                } else {
+                    fixed_length::<$t, N>(u)
+                }
+            }
+
+            // the 0.3 bound flags check against |MIN| and MAX of the signed counterpart
+            #[inline]
+            fn unsinged_decimal_core(u: &[u8], neg_max_check: bool, pos_max_check: bool) -> Result<Self, ParseIntErr> {
+                let val = Self::unsigned_decimal_core(u)?;
+                if neg_max_check && val > <$signed>::MIN.unsigned_abs() {
+                    Err(ParseIntErr::NegOverflow)
+                } else if pos_max_check && val > <$signed>::MAX as $t {
+                    Err(ParseIntErr::Overflow)
+                } else {
+                    Ok(val)
+                }
+            }
+        }
+    };
+}
+
+impl_unsigned_biscuit!(u8, i8, [1 2 3]);
+impl_unsigned_biscuit!(u16, i16, [1 2 3 4 5]);
+impl_unsigned_biscuit!(u32, i32, [1 2 3 4 5 6 7 8 9 10]);
+impl_unsigned_biscuit!(u64, i64, [
+    1 2 3 4 5 6 7 8 9 10
+    11 12 13 14 15 16 17 18 19 20
+]);
+impl_unsigned_biscuit!(u128, i128, [
+    1 2 3 4 5 6 7 8 9 10
+    11 12 13 14 15 16 17 18 19 20
+    21 22 23 24 25 26 27 28 29 30
+    31 32 33 34 35 36 37 38 39
+]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::IntErrorKind;
+    use std::str::FromStr;
+
+    fn expected<T: FromStr<Err = std::num::ParseIntError>>(input: &str) -> Result<T, ParseIntErr> {
+        input.parse::<T>().map_err(|e| match e.kind() {
+            IntErrorKind::Empty => ParseIntErr::Empty,
+            IntErrorKind::PosOverflow => ParseIntErr::Overflow,
+            _ => ParseIntErr::NonDecimal,
+        })
+    }
+
+    // The per-length arms of the 0.3 `u128` kernel, kept as the reference for
+    // `fixed_length`. The narrower types used the same chunks in 0.3.
+    fn hand_written(u: &[u8]) -> Result<u128, ParseIntErr> {
         let length = u.len();
         if length <= 8 {
             if length <= 4 {
@@ -227,12 +403,12 @@ impl Biscuit for u128 {
                     let mid = mid_chunk * 10_000_000;
                     let lower = lower_chunk * 1_000;
                     let tail = tail_chunk * 10;
-                    
+
                     Ok(upper + mid + lower + tail + last)
                 },
                 32 => {
                     let upper_chunk = checked_conversion_u128(&u[..16])?;
-                    let lower_chunk = checked_conversion_u128(&u[16..])? as u128;
+                    let lower_chunk = checked_conversion_u128(&u[16..])?;
                     let upper = upper_chunk * 10_000_000_000_000_000;
                     Ok(upper + lower_chunk)
                 },
@@ -258,8 +434,8 @@ impl Biscuit for u128 {
                     }
                 },
                 35 => {
-                    let upper_chunk = checked_conversion_u128(&u[..16])? as u128;
-                    let mid_chunk = checked_conversion_u128(&u[16..32])? as u128;
+                    let upper_chunk = checked_conversion_u128(&u[..16])?;
+                    let mid_chunk = checked_conversion_u128(&u[16..32])?;
                     let lower_chunk = checked_conversion_u16(&u[32..34])? as u128;
                     let last_chunk = checked_conversion_u8(&u[34..])? as u128;
                     let upper = upper_chunk.wrapping_mul(10_000_000_000_000_000_000);
@@ -311,27 +487,19 @@ impl Biscuit for u128 {
                     let lower_chunk = checked_conversion_u32(&u[32..36])? as u128;
                     let tail_chunk = checked_conversion_u16(&u[36..38])? as u128;
                     let last = checked_conversion_u8(&u[38..])? as u128;
-                    let upper = upper_chunk.wrapping_mul(100_000_000_000_000_000_000_000);
+                    // 0.3 wrapped here, which let some 39-digit overflows through
+                    let upper = upper_chunk.checked_mul(100_000_000_000_000_000_000_000).ok_or(ParseIntErr::Overflow)?;
                     let mid = mid_chunk * 10_000_000;
                     let lower = lower_chunk * 1000;
                     let tail = tail_chunk * 10;
                     let res = mid + lower + tail + last;
-                    if let Some(res) = upper.checked_add(res) {
-                        if neg_max_check && res > I128_MIN_ABS_AS_U128 {
-                            Err(ParseIntErr::NegOverflow)
-                        } else if pos_max_check && res > I128_MAX_AS_U128 {
-                            Err(ParseIntErr::Overflow)
-                        } else {
-                            Ok(res)
-                        }
-                    } else {
-                        Err(ParseIntErr::Overflow)
-                    }
+                    upper.checked_add(res).ok_or(ParseIntErr::Overflow)
                 },
                 _ => {
                     let mut length = u.len();
                     let mut start = 0;
-                    while start < length && u[start] == b'0' {
+                    // 0.3 also dropped the last zero and read "000..0" as Empty
+                    while start < length - 1 && u[start] == b'0' {
                         start += 1;
                     }
                     length -= start;
@@ -339,319 +507,107 @@ impl Biscuit for u128 {
                         return Err(ParseIntErr::Overflow)
                     }
                     let u = &u[start..];
-                    Self::unsinged_decimal_core(u, neg_max_check, pos_max_check)
-                },    
+                    hand_written(u)
+                },
             }
-        }        
+        }
     }
-}
 
-impl Biscuit for u64 {
-    fn unsinged_decimal_core(u: &[u8], neg_max_check: bool, pos_max_check: bool) -> Result<Self, crate::error::ParseIntErr> {
-        let length = u.len();
-        if length > 8 {
-            match length {
-                9 => {
-                    let upper_chunk = checked_conversion_u64(&u[..8])?;
-                    let lower_chunk = checked_conversion_u8(&u[8..])?;
-                    Ok(upper_chunk as u64 * 10 + lower_chunk as u64)
-                },
-                10 => {
-                    let upper_chunk = checked_conversion_u64(&u[..8])?;
-                    let lower_chunk = checked_conversion_u16(&u[8..])?;
-                    Ok(upper_chunk as u64 * 100 + lower_chunk as u64)
-                },
-                11 => {
-                    let upper_chunk = checked_conversion_u64(&u[..8])?;
-                    let mid_chunk = checked_conversion_u16(&u[8..10])?;
-                    let lower_chunk = checked_conversion_u8(&u[10..])?;
-                    Ok(upper_chunk as u64 * 1_000 + mid_chunk as u64 * 10 + lower_chunk as u64)
-                },
-                12 => {
-                    let upper_chunk = checked_conversion_u64(&u[..8])?;
-                    let lower_chunk = checked_conversion_u32(&u[8..])?;
-                    Ok(upper_chunk as u64 * 10_000 + lower_chunk as u64)
-                },
-                13 => {
-                    let upper_chunk = checked_conversion_u64(&u[..8])?;
-                    let mid_chunk = checked_conversion_u32(&u[8..12])?;
-                    let lower_chunk = checked_conversion_u8(&u[12..])?;
-                    Ok(upper_chunk as u64 * 100_000 + mid_chunk as u64 * 10 + lower_chunk as u64)
-                },
-                14 => {
-                    let upper_chunk = checked_conversion_u64(&u[..8])?;
-                    let mid_chunk = checked_conversion_u32(&u[8..12])?;
-                    let lower_chunk = checked_conversion_u16(&u[12..])?;
-                    Ok(upper_chunk as u64 * 1_000_000 + mid_chunk as u64 * 100 + lower_chunk as u64)
-                },
-                15 => {
-                    let upper_chunk = checked_conversion_u64(&u[..8])?;
-                    let mid_chunk = checked_conversion_u32(&u[8..12])?;
-                    let lower_chunk = checked_conversion_u16(&u[12..14])?;
-                    let last_chunk = checked_conversion_u8(&u[14..])?;
-                    
-                    let upper = (upper_chunk as u64).wrapping_mul(10_000_000);
-                    let mid = mid_chunk as u64 * 1_000;
-                    let lower = lower_chunk as u64 * 10;
-                    let last = last_chunk as u64;
-                    let res = last + lower + mid;
-                    if let Some(res) = upper.checked_add(res) {
-                        Ok(res)
-                    } else {
-                        Err(ParseIntErr::Overflow)
-                    }
 
-                },
-                16 => checked_conversion_u128(u).map(|val| val as u64),
-                17 => {
-                    let upper_chunk = checked_conversion_u128(&u[..16])?;
-                    let lower_chunk = checked_conversion_u8(&u[16..])?;
-                    Ok(upper_chunk as u64 * 10 + lower_chunk as u64)
-                },
-                18 => {
-                    let upper_chunk = checked_conversion_u128(&u[..16])?;
-                    let lower_chunk = checked_conversion_u16(&u[16..])?;
-                    Ok(upper_chunk as u64 * 100 + lower_chunk as u64)
-                },
-                19 => {
-                    let upper_chunk = checked_conversion_u128(&u[..16])?;
-                    let mid_chunk = checked_conversion_u16(&u[16..18])?;
-                    let lower_chunk = checked_conversion_u8(&u[18..])?;
-                    let upper = upper_chunk as u64 * 1_000;
-                    let mid = mid_chunk as u64 * 10;
-                    let lower = lower_chunk as u64;
-                    let res = lower + mid;
-                    if let Some(res) = upper.checked_add(res) {
-                        if neg_max_check && res > I64_MIN_ABS_AS_U64 {
-                            Err(ParseIntErr::NegOverflow)
-                        } else if pos_max_check && res > I64_MAX_AS_U64 {
-                            Err(ParseIntErr::Overflow)
-                        } else {
-                            Ok(res)
-                        }
-                    } else {
-                        Err(ParseIntErr::Overflow)
-                    }
-                },
-                20 => {
-                    let upper_chunk = checked_conversion_u128(&u[..16])?;
-                    let lower_chunk = checked_conversion_u32(&u[16..])?;
-                    let upper = (upper_chunk as u64).wrapping_mul(10_000);
-                    if let Some(res) = upper.checked_add(lower_chunk as u64) {
-                        if neg_max_check && res > I64_MIN_ABS_AS_U64 {
-                            Err(ParseIntErr::NegOverflow)
-                        } else if pos_max_check && res > I64_MAX_AS_U64 {
-                            Err(ParseIntErr::Overflow)
-                        } else {
-                            Ok(res)
-                        }
-                    } else {
-                        Err(ParseIntErr::Overflow)
-                    }
-                },
-                _ => {
-                    let mut length = length;
-                    let mut start = 0;
-                    while start < length && u[start] == b'0' {
-                        start += 1;
-                    }
-                    length -= start;
-                    if length > 20 {
-                        return Err(ParseIntErr::Overflow)
-                    }
-                    let u = &u[start..];
-                    Self::unsinged_decimal_core(u, neg_max_check, pos_max_check)
-                },
+    // 0.3 read the whole input for any length up to MAX_DIGITS and stripped leading
+    // zeros before the length check above it
+    fn reference<T: DecimalAccumulator + TryFrom<u128>>(u: &[u8]) -> Result<T, ParseIntErr> {
+        let mut start = 0;
+        if u.len() > T::MAX_DIGITS {
+            while start < u.len() - 1 && u[start] == b'0' {
+                start += 1;
             }
-        } else {
-            if length > 4 {
-                match length {
-                    5 => {
-                        let upper_chunk = checked_conversion_u32(&u[..4])?;
-                        let lower_chunk = checked_conversion_u8(&u[4..])?;
-                        Ok((upper_chunk) as u64 * 10 + lower_chunk as u64)
-                    },
-                    6 => {
-                        let upper_chunk = checked_conversion_u32(&u[..4])?;
-                        let lower_chunk = checked_conversion_u16(&u[4..])?;
-                        Ok(upper_chunk as u64 * 100 + lower_chunk as u64)
-                    },
-                    7 => {
-                        let upper_chunk = checked_conversion_u32(&u[..4])?;
-                        let mid_chunk = checked_conversion_u16(&u[4..6])?;
-                        let lower_chunk = checked_conversion_u8(&u[6..])?;
-                        Ok(upper_chunk as u64 * 1_000 + mid_chunk as u64 * 10 + lower_chunk as u64)
-                    },
-                    _ => checked_conversion_u64(u),
-                }
-            } else {
-                match length {
-                    0 => Err(ParseIntErr::Empty),
-                    1 => checked_conversion_u8(u).map(|val| val as u64),
-                    2 => checked_conversion_u16(u).map(|val| val as u64),
-                    3 => {
-                        let upper_chunk = checked_conversion_u16(&u[..2])?;
-                        let lower_chunk = checked_conversion_u8(&u[2..])?;
-                        Ok((upper_chunk as u64) * 10 + lower_chunk as u64)
-                    }
-                    _ => checked_conversion_u32(u).map(|val| val as u64),
-                }
-            } 
-        } 
-    }
-}
-
-impl Biscuit for u32 {
-    fn unsinged_decimal_core(u: &[u8], neg_max_check: bool, pos_max_check: bool) -> Result<Self, crate::error::ParseIntErr> {
-        let length = u.len();
-        match length {
-            0 => Err(ParseIntErr::Empty),
-            1 => checked_conversion_u8(u).map(|val| val as u32),
-            2 => checked_conversion_u16(u).map(|val| val as u32),
-            3 => {
-                let upper_chunk = checked_conversion_u16(&u[..2])?;
-                let lower_chunk = checked_conversion_u8(&u[2..])?;
-                Ok((upper_chunk as u32) * 10 + lower_chunk as u32)
+            if u.len() - start > T::MAX_DIGITS {
+                return Err(ParseIntErr::Overflow);
             }
-            4 => checked_conversion_u32(u),
-            5 => {
-                let upper_chunk = checked_conversion_u32(&u[..4])?;
-                let lower_chunk = checked_conversion_u8(&u[4..])?;
-                Ok((upper_chunk) as u32 * 10 + lower_chunk as u32)
-            },
-            6 => {
-                let upper_chunk = checked_conversion_u32(&u[..4])?;
-                let lower_chunk = checked_conversion_u16(&u[4..])?;
-                Ok(upper_chunk as u32 * 100 + lower_chunk as u32)
-            },
-            7 => {
-                let upper_chunk = checked_conversion_u32(&u[..4])?;
-                let mid_chunk = checked_conversion_u16(&u[4..6])?;
-                let lower_chunk = checked_conversion_u8(&u[6..])?;
-                Ok(upper_chunk as u32 * 1_000 + mid_chunk as u32 * 10 + lower_chunk as u32)
-            },
-            8 => checked_conversion_u64(u).map(|val| val as u32),
-            9 => {
-                let upper_chunk = checked_conversion_u64(&u[..8])?;
-                let lower_chunk = checked_conversion_u8(&u[8..])?;
-                Ok(upper_chunk as u32 * 10 + lower_chunk as u32)
-            },
-            10 => {
-                let upper_chunk = checked_conversion_u64(&u[..8])?;
-                let lower_chunk = checked_conversion_u16(&u[8..])?;
-                let upper = (upper_chunk as u32).wrapping_mul(100);
-                if let Some(res) = upper.checked_add(lower_chunk as u32) {
-                    if neg_max_check && res > I32_MIN_ABS_AS_U32 {
-                        Err(ParseIntErr::NegOverflow)
-                    } else if pos_max_check && res > I32_MAX_AS_U32 {
-                        Err(ParseIntErr::Overflow)
-                    } else {
-                        Ok(res)
-                    }
-                } else {
-                    Err(ParseIntErr::Overflow)
-                }
-            },
-            _ => {
-                let mut length = length;
-                let mut start = 0;
-                while start < length && u[start] == b'0' {
-                    start += 1;
-                }
-                length -= start;
-                if length > 10 {
-                    return Err(ParseIntErr::Overflow)
-                }
-                let u = &u[start..];
-                Self::unsinged_decimal_core(u, neg_max_check, pos_max_check)
-            },
         }
+        hand_written(&u[start..]).and_then(|val| T::try_from(val).map_err(|_| ParseIntErr::Overflow))
     }
-}
 
-impl Biscuit for u16 {
-    fn unsinged_decimal_core(u: &[u8], neg_max_check: bool, pos_max_check: bool) -> Result<Self, crate::error::ParseIntErr> {
-        match u.len() {
-            0 => Err(ParseIntErr::Empty),
-            1 => checked_conversion_u8(u).map(|val| val as u16),
-            2 => checked_conversion_u16(u),
-            3 => {
-                let upper_chunk = checked_conversion_u16(&u[..2])?;
-                let lower_chunk = checked_conversion_u8(&u[2..])?;
-                Ok((upper_chunk as u16) * 10 + lower_chunk as u16)
+    fn check_against_reference<T>(max: u128)
+    where
+        T: Biscuit + DecimalAccumulator + TryFrom<u128> + PartialEq + std::fmt::Debug,
+    {
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut random_digit = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            b'0' + (seed % 10) as u8
+        };
+        let mut seen = [false; 4];
+        let mut check = |u: &[u8]| {
+            let result = T::parse_decimal(u);
+            assert_eq!(result, reference::<T>(u), "Failed for {:?}", String::from_utf8_lossy(u));
+            let class = match result {
+                Ok(_) => 0,
+                Err(ParseIntErr::Empty) => 1,
+                Err(ParseIntErr::NonDecimal) => 2,
+                Err(_) => 3,
+            };
+            seen[class] = true;
+        };
+
+        let max_digits = <T as DecimalAccumulator>::MAX_DIGITS;
+        // u128::MAX + 1 does not fit, every MAX ends in 5
+        assert_eq!(max % 10, 5);
+        let above_max = format!("{}6", max / 10);
+        let values = [max.to_string(), above_max, 10u128.pow(max_digits as u32 - 1).to_string()];
+        for length in 0..=max_digits + 8 {
+            let mut inputs: Vec<Vec<u8>> = (0..64).map(|_| (0..length).map(|_| random_digit()).collect()).collect();
+            for digits in &values {
+                if digits.len() <= length {
+                    inputs.push(format!("{:0>width$}", digits, width = length).into_bytes());
+                }
             }
-            4 => checked_conversion_u32(u).map(|val| val as u16),
-            5 => {
-                let upper_chunk = checked_conversion_u32(&u[..4])?;
-                let lower_chunk = checked_conversion_u8(&u[4..])?;
-                let upper_chunk = (upper_chunk as u16).wrapping_mul(10);
-                if let Some(res) = upper_chunk.checked_add(lower_chunk as u16) {
-                    if neg_max_check && res > I16_MIN_ABS_AS_U16 {
-                        Err(ParseIntErr::NegOverflow)
-                    } else if pos_max_check && res > I16_MAX_AS_U16 {
-                        Err(ParseIntErr::Overflow)
-                    } else {
-                        Ok(res)
+            inputs.push(vec![b'9'; length]);
+            inputs.push(vec![b'0'; length]);
+            for input in inputs {
+                check(&input);
+                for pos in 0..length {
+                    for byte in [b'/', b':', b' ', b'-', b'a', 0x00, 0x80, 0xFF] {
+                        let mut input = input.clone();
+                        input[pos] = byte;
+                        check(&input);
                     }
-                } else {
-                    Err(ParseIntErr::Overflow)
-                }
-            },
-            _ => {
-                let mut length = u.len();
-                let mut start = 0;
-                while start < length && u[start] == b'0' {
-                    start += 1;
-                }
-                length -= start;
-                if length > 5 {
-                    return Err(ParseIntErr::Overflow)
                 }
-                let u = &u[start..];
-                Self::unsinged_decimal_core(u, neg_max_check, pos_max_check)
-            },
+            }
         }
+        assert_eq!(seen, [true; 4], "Ok, Empty, NonDecimal and Overflow should all be reached");
     }
-}
 
-impl Biscuit for u8 {
-    fn unsinged_decimal_core(u: &[u8], neg_max_check: bool, pos_max_check: bool) -> Result<Self, crate::error::ParseIntErr> {
-        let length = u.len();
-        match length {
-            0 => Err(ParseIntErr::Empty),
-            1 => checked_conversion_u8(u),
-            2 => checked_conversion_u16(u).map(|val| val as u8),
-            3 => {
-                let upper_chunk = checked_conversion_u16(&u[..2])?;
-                let lower_chunk = checked_conversion_u8(&u[2..])?;
-                let upper_chunk = (upper_chunk as u8).wrapping_mul(10);
-                match upper_chunk.checked_add(lower_chunk) {
-                    Some(res) => {
-                        if neg_max_check && res > I8_MIN_ABS_AS_U8 {
-                            Err(ParseIntErr::NegOverflow)
-                        } else if pos_max_check && res > I8_MAX_AS_U8 {
-                            Err(ParseIntErr::Overflow)
-                        } else {
-                            Ok(res)
-                        }
-                    },
-                    None => Err(ParseIntErr::Overflow),
-                }
+    #[test]
+    fn test_against_hand_written() {
+        check_against_reference::<u8>(u8::MAX as u128);
+        check_against_reference::<u16>(u16::MAX as u128);
+        check_against_reference::<u32>(u32::MAX as u128);
+        check_against_reference::<u64>(u64::MAX as u128);
+        check_against_reference::<u128>(u128::MAX);
+    }
+
+    fn check_lengths<T>()
+    where
+        T: Biscuit + FromStr<Err = std::num::ParseIntError> + PartialEq + std::fmt::Debug,
+    {
+        for length in 0..=64 {
+            for digits in ["9876543210", "0", "9", "1000000000", "0000000001"] {
+                let input: String = digits.chars().cycle().take(length).collect();
+                assert_eq!(T::parse_decimal(input.as_bytes()), expected::<T>(&input), "Failed for {}", input);
             }
-            _ => {
-                let mut length = length;
-                let mut start = 0;
-                while start < length && u[start] == b'0' {
-                    start += 1;
-                }
-                length -= start;
-                if length > 3 {
-                    return Err(ParseIntErr::Overflow)
-                }
-                let u = &u[start..];
-                Self::unsinged_decimal_core(u, neg_max_check, pos_max_check)
-            },
         }
     }
-}
 
+    #[test]
+    fn test_every_length() {
+        check_lengths::<u8>();
+        check_lengths::<u16>();
+        check_lengths::<u32>();
+        check_lengths::<u64>();
+        check_lengths::<u128>();
+    }
+}
@@ -1,39 +1,110 @@
-// from_le_bytes version for u128 (up to 16 bytes)
+/// from_le_bytes version for u128 (up to 16 bytes, left-padded with b'0')
+///
+/// # Panics
+/// Panics if the input is longer than 16 bytes.
 #[inline]
 #[must_use]
-pub(crate) fn le_bytes_to_u128(input: &[u8]) -> u128 {
-    let mut bytes = [0u8; 16];
+pub fn le_bytes_to_u128(input: &[u8]) -> u128 {
+    let mut bytes = [b'0'; 16];
     let start = 16 - input.len();
     bytes[start..].copy_from_slice(input);
     u128::from_le_bytes(bytes)
 }
 
-// from_le_bytes version for u64 (up to 8 bytes)
+/// from_le_bytes version for u64 (up to 8 bytes, left-padded with b'0')
+///
+/// # Panics
+/// Panics if the input is longer than 8 bytes.
 #[inline]
 #[must_use]
-pub(crate) fn le_bytes_to_u64(input: &[u8]) -> u64 {
-    let mut bytes = [0u8; 8];
+pub fn le_bytes_to_u64(input: &[u8]) -> u64 {
+    let mut bytes = [b'0'; 8];
     let start = 8 - input.len();
     bytes[start..].copy_from_slice(input);
     u64::from_le_bytes(bytes)
 }
 
-// from_le_bytes version for u32 (up to 4 bytes)
+/// from_le_bytes version for u32 (up to 4 bytes, left-padded with b'0')
+///
+/// # Panics
+/// Panics if the input is longer than 4 bytes.
 #[inline]
 #[must_use]
-pub(crate) fn le_bytes_to_u32(input: &[u8]) -> u32 {
-    let mut bytes = [0u8; 4];
+pub fn le_bytes_to_u32(input: &[u8]) -> u32 {
+    let mut bytes = [b'0'; 4];
     let start = 4 - input.len();
     bytes[start..].copy_from_slice(input);
     u32::from_le_bytes(bytes)
 }
 
-// from_le_bytes version for u16 (up to 2 bytes)
+/// from_le_bytes version for u16 (up to 2 bytes, left-padded with b'0')
+///
+/// # Panics
+/// Panics if the input is longer than 2 bytes.
 #[inline]
 #[must_use]
-pub(crate) fn le_bytes_to_u16(input: &[u8]) -> u16 {
-    let mut bytes = [0u8; 2];
+pub fn le_bytes_to_u16(input: &[u8]) -> u16 {
+    let mut bytes = [b'0'; 2];
     let start = 2 - input.len();
     bytes[start..].copy_from_slice(input);
     u16::from_le_bytes(bytes)
-}
\ No newline at end of file
+}
+
+const ZERO_PAD_U64: u64 = 0x3030303030303030;
+const ZERO_PAD_U128: u128 = 0x30303030303030303030303030303030;
+
+// bytes of u (1..=8 of them) from the lowest byte up, using two overlapping fixed-width loads
+#[inline]
+fn load_raw_u64(u: &[u8]) -> u64 {
+    let length = u.len();
+    if length >= 4 {
+        let first = u32::from_le_bytes([u[0], u[1], u[2], u[3]]) as u64;
+        let last = u32::from_le_bytes([u[length - 4], u[length - 3], u[length - 2], u[length - 1]]) as u64;
+        first | (last << (8 * (length - 4)))
+    } else if length >= 2 {
+        let first = u16::from_le_bytes([u[0], u[1]]) as u64;
+        let last = u16::from_le_bytes([u[length - 2], u[length - 1]]) as u64;
+        first | (last << (8 * (length - 2)))
+    } else {
+        u[0] as u64
+    }
+}
+
+// same layout as le_bytes_to_u64 without the variable-length copy, u.len() in 1..=8
+#[inline]
+pub(crate) fn load_u64(u: &[u8]) -> u64 {
+    let length = u.len();
+    let pad = ZERO_PAD_U64.checked_shr(8 * length as u32).unwrap_or(0);
+    (load_raw_u64(u) << (8 * (8 - length))) | pad
+}
+
+// same layout as le_bytes_to_u128 without the variable-length copy, u.len() in 1..=16
+#[inline]
+pub(crate) fn load_u128(u: &[u8]) -> u128 {
+    let length = u.len();
+    let raw = if length >= 8 {
+        let first = u64::from_le_bytes(u[..8].try_into().unwrap()) as u128;
+        let last = u64::from_le_bytes(u[length - 8..].try_into().unwrap()) as u128;
+        first | (last << (8 * (length - 8)))
+    } else {
+        load_raw_u64(u) as u128
+    };
+    let pad = ZERO_PAD_U128.checked_shr(8 * length as u32).unwrap_or(0);
+    (raw << (8 * (16 - length))) | pad
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load() {
+        for length in 1..=16 {
+            let u: Vec<u8> = (0..length as u8).map(|x| b'a' + x).collect();
+            if length <= 8 {
+                assert_eq!(load_u64(&u), le_bytes_to_u64(&u), "Failed for length {}", length);
+            }
+            assert_eq!(load_u128(&u), le_bytes_to_u128(&u), "Failed for length {}", length);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Validation without conversion
+//!
+//! The input is scanned in wide chunks with the same SWAR checks used by the parsers.
+//!
+//! ```rust
+//! use biscuit_converter::validate::{is_decimal, first_non_digit, leading_digit_count};
+//!
+//! assert!(is_decimal(b"0123456789"));
+//! assert!(!is_decimal(b"12.5"));
+//! assert_eq!(first_non_digit(b"12.5"), Some(2));
+//! assert_eq!(leading_digit_count(b"20240910,AAPL"), 8);
+//! ```
+use crate::little_endian_decimal::{
+    check_decimal_bit_u16,
+    check_decimal_bit_u32,
+    check_decimal_bit_u64,
+    check_decimal_bit_u128,
+    non_decimal_mask_u64,
+};
+use crate::utils::{
+    le_bytes_to_u16,
+    le_bytes_to_u32,
+    le_bytes_to_u64,
+    le_bytes_to_u128,
+};
+
+/// `true` iff the input is non-empty and consists only of ascii digits
+#[inline]
+pub fn is_decimal(u: &[u8]) -> bool {
+    if u.is_empty() {
+        return false;
+    }
+
+    let mut chunks = u.chunks_exact(16);
+    for chunk in chunks.by_ref() {
+        if !check_decimal_bit_u128(le_bytes_to_u128(chunk)) {
+            return false;
+        }
+    }
+
+    let rest = chunks.remainder();
+    match rest.len() {
+        0 => true,
+        1..=2 => check_decimal_bit_u16(le_bytes_to_u16(rest)),
+        3..=4 => check_decimal_bit_u32(le_bytes_to_u32(rest)),
+        5..=8 => check_decimal_bit_u64(le_bytes_to_u64(rest)),
+        _ => check_decimal_bit_u128(le_bytes_to_u128(rest)),
+    }
+}
+
+/// Index of the first byte that is not an ascii digit, `None` if there is none
+#[inline]
+pub fn first_non_digit(u: &[u8]) -> Option<usize> {
+    let mut chunks = u.chunks_exact(8);
+    let mut offset = 0;
+    for chunk in chunks.by_ref() {
+        let mask = non_decimal_mask_u64(le_bytes_to_u64(chunk));
+        if mask != 0 {
+            return Some(offset + (mask.trailing_zeros() / 8) as usize);
+        }
+        offset += 8;
+    }
+
+    let rest = chunks.remainder();
+    if rest.is_empty() {
+        return None;
+    }
+    // the remainder is left-padded, so its first byte sits at 8 - rest.len()
+    let mask = non_decimal_mask_u64(le_bytes_to_u64(rest));
+    if mask != 0 {
+        Some(offset + (mask.trailing_zeros() / 8) as usize - (8 - rest.len()))
+    } else {
+        None
+    }
+}
+
+/// Number of ascii digits at the start of the input
+#[inline]
+pub fn leading_digit_count(u: &[u8]) -> usize {
+    first_non_digit(u).unwrap_or(u.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_first_non_digit(u: &[u8]) -> Option<usize> {
+        u.iter().position(|x| !x.is_ascii_digit())
+    }
+
+    #[test]
+    fn test_is_decimal() {
+        assert!(!is_decimal(b""));
+        for length in 1..=64 {
+            let mut u = vec![b'7'; length];
+            assert!(is_decimal(&u), "Failed for length {}", length);
+            for pos in 0..length {
+                for byte in [b'/', b':', b' ', b'-', 0u8, 0xB0] {
+                    u[pos] = byte;
+                    assert!(!is_decimal(&u), "Failed for {:?}", u);
+                }
+                u[pos] = b'0';
+            }
+        }
+    }
+
+    #[test]
+    fn test_first_non_digit() {
+        assert_eq!(first_non_digit(b""), None);
+        for length in 1..=40 {
+            let mut u = vec![b'9'; length];
+            assert_eq!(first_non_digit(&u), None);
+            assert_eq!(leading_digit_count(&u), length);
+            for pos in (0..length).rev() {
+                u[pos] = b',';
+                assert_eq!(first_non_digit(&u), naive_first_non_digit(&u), "Failed for {:?}", u);
+                assert_eq!(leading_digit_count(&u), pos);
+            }
+        }
+    }
+}
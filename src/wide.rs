@@ -0,0 +1,398 @@
+//! 256-bit integers for values beyond `u128`, e.g., on-chain token amounts
+//!
+//! [`U256`] and [`I256`] are plain storage types with just enough arithmetic to parse
+//! and print them. The value is kept as four little-endian `u64` limbs, i.e., `limbs[0]`
+//! is the least significant one, and [`I256`] is its two's complement.
+//!
+//! ```rust
+//! use biscuit_converter::Biscuit;
+//! use biscuit_converter::wide::{U256, I256};
+//!
+//! let wei = U256::parse_decimal(b"1000000000000000000000000000000").unwrap();
+//! assert_eq!(wei.to_string(), "1000000000000000000000000000000");
+//! assert_eq!(U256::parse_decimal(b"42"), Ok(U256::from(42u64)));
+//!
+//! let delta = I256::parse_decimal(b"-1000000000000000000000000000000").unwrap();
+//! assert!(delta.is_negative());
+//! assert_eq!(delta.unsigned_abs(), wei);
+//! ```
+use crate::Biscuit;
+use crate::sealed::Sealed;
+use crate::unsigned_decimal::UnsignedCore;
+use crate::error::ParseIntErr;
+use crate::little_endian_decimal::{
+    check_decimal_bit_u128,
+    sixteen_to_u128,
+};
+use crate::utils::load_u128;
+use crate::format::write_u64;
+use std::cmp::Ordering;
+use std::fmt;
+
+const TEN_POW_16: u64 = 10_000_000_000_000_000;
+
+/// Unsigned 256-bit integer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct U256 {
+    limbs: [u64; 4],
+}
+
+impl U256 {
+    pub const ZERO: U256 = U256 { limbs: [0; 4] };
+    pub const MAX: U256 = U256 { limbs: [u64::MAX; 4] };
+    /// number of digits of `U256::MAX`
+    pub const MAX_DIGITS: usize = 78;
+
+    /// `limbs[0]` is the least significant limb
+    #[inline]
+    pub const fn from_limbs(limbs: [u64; 4]) -> Self {
+        U256 { limbs }
+    }
+
+    #[inline]
+    pub const fn into_limbs(self) -> [u64; 4] {
+        self.limbs
+    }
+
+    #[inline]
+    pub const fn is_zero(&self) -> bool {
+        (self.limbs[0] | self.limbs[1] | self.limbs[2] | self.limbs[3]) == 0
+    }
+
+    /// `self * mul + add`, `None` on overflow
+    #[inline]
+    fn checked_mul_add_u64(self, mul: u64, add: u64) -> Option<Self> {
+        let mut limbs = [0u64; 4];
+        let mut carry = add as u128;
+        for (res, limb) in limbs.iter_mut().zip(self.limbs) {
+            let t = limb as u128 * mul as u128 + carry;
+            *res = t as u64;
+            carry = t >> 64;
+        }
+        if carry != 0 { None } else { Some(U256 { limbs }) }
+    }
+
+    /// quotient and remainder of the division by `div`
+    #[inline]
+    fn div_rem_u64(self, div: u64) -> (Self, u64) {
+        let mut limbs = [0u64; 4];
+        let mut rem = 0u128;
+        for i in (0..4).rev() {
+            let t = (rem << 64) | self.limbs[i] as u128;
+            limbs[i] = (t / div as u128) as u64;
+            rem = t % div as u128;
+        }
+        (U256 { limbs }, rem as u64)
+    }
+
+    #[inline]
+    fn wrapping_neg(self) -> Self {
+        let mut limbs = [0u64; 4];
+        let mut carry = true;
+        for (res, limb) in limbs.iter_mut().zip(self.limbs) {
+            let (val, c) = (!limb).overflowing_add(carry as u64);
+            *res = val;
+            carry = c;
+        }
+        U256 { limbs }
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs.iter().rev().cmp(other.limbs.iter().rev())
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<u128> for U256 {
+    #[inline]
+    fn from(val: u128) -> Self {
+        U256 { limbs: [val as u64, (val >> 64) as u64, 0, 0] }
+    }
+}
+
+macro_rules! impl_u256_from {
+    ($($t:ty)*) => {
+        $(
+            impl From<$t> for U256 {
+                #[inline]
+                fn from(val: $t) -> Self {
+                    U256::from(val as u128)
+                }
+            }
+        )*
+    };
+}
+
+impl_u256_from!(u8 u16 u32 u64);
+
+impl TryFrom<U256> for u128 {
+    type Error = ParseIntErr;
+
+    #[inline]
+    fn try_from(val: U256) -> Result<Self, Self::Error> {
+        if (val.limbs[2] | val.limbs[3]) != 0 {
+            return Err(ParseIntErr::Overflow);
+        }
+        Ok(val.limbs[0] as u128 | (val.limbs[1] as u128) << 64)
+    }
+}
+
+impl U256 {
+    // 16-digit groups like the primitive path in `format`, written straight to the formatter;
+    // padding goes through `pad_integral` on a stack buffer
+    fn fmt_decimal(&self, nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // least significant first, 78 digits fit in 5 groups
+        let mut groups = [0u64; 5];
+        let mut count = 0;
+        let mut rest = *self;
+        loop {
+            let (quotient, rem) = rest.div_rem_u64(TEN_POW_16);
+            groups[count] = rem;
+            count += 1;
+            if quotient.is_zero() {
+                break;
+            }
+            rest = quotient;
+        }
+
+        if f.width().is_none() && !f.sign_plus() {
+            if !nonnegative {
+                f.write_str("-")?;
+            }
+            write!(f, "{}", groups[count - 1])?;
+            for group in groups[..count - 1].iter().rev() {
+                write!(f, "{:016}", group)?;
+            }
+            return Ok(());
+        }
+
+        let mut buf = [b'0'; 80];
+        let mut start = buf.len();
+        for group in groups[..count - 1].iter() {
+            write_u64(*group, &mut buf, start);
+            start -= 16;
+        }
+        start = write_u64(groups[count - 1], &mut buf, start);
+        // only ASCII digits were written
+        f.pad_integral(nonnegative, "", std::str::from_utf8(&buf[start..]).unwrap())
+    }
+}
+
+impl fmt::Display for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_decimal(true, f)
+    }
+}
+
+/// Signed 256-bit integer in two's complement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct I256 {
+    bits: U256,
+}
+
+impl I256 {
+    pub const ZERO: I256 = I256 { bits: U256::ZERO };
+    pub const MAX: I256 = I256 { bits: U256::from_limbs([u64::MAX, u64::MAX, u64::MAX, i64::MAX as u64]) };
+    pub const MIN: I256 = I256 { bits: U256::from_limbs([0, 0, 0, i64::MIN as u64]) };
+    /// number of digits of `I256::MIN` and `I256::MAX`, without the sign
+    pub const MAX_DIGITS: usize = 77;
+
+    /// two's complement limbs, `limbs[0]` is the least significant limb
+    #[inline]
+    pub const fn from_limbs(limbs: [u64; 4]) -> Self {
+        I256 { bits: U256::from_limbs(limbs) }
+    }
+
+    #[inline]
+    pub const fn into_limbs(self) -> [u64; 4] {
+        self.bits.into_limbs()
+    }
+
+    #[inline]
+    pub const fn is_negative(&self) -> bool {
+        (self.bits.limbs[3] as i64) < 0
+    }
+
+    #[inline]
+    pub fn unsigned_abs(self) -> U256 {
+        if self.is_negative() { self.bits.wrapping_neg() } else { self.bits }
+    }
+}
+
+impl Ord for I256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.is_negative().cmp(&self.is_negative()).then(self.bits.cmp(&other.bits))
+    }
+}
+
+impl PartialOrd for I256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<i128> for I256 {
+    #[inline]
+    fn from(val: i128) -> Self {
+        let sign = (val >> 127) as u64;
+        I256 { bits: U256::from_limbs([val as u64, (val >> 64) as u64, sign, sign]) }
+    }
+}
+
+macro_rules! impl_i256_from {
+    ($($t:ty)*) => {
+        $(
+            impl From<$t> for I256 {
+                #[inline]
+                fn from(val: $t) -> Self {
+                    I256::from(val as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_i256_from!(i8 i16 i32 i64 u8 u16 u32 u64);
+
+impl fmt::Display for I256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.unsigned_abs().fmt_decimal(!self.is_negative(), f)
+    }
+}
+
+// |I256::MIN| and I256::MAX as unsigned values
+const NEG_BOUND: U256 = U256::from_limbs([0, 0, 0, i64::MIN as u64]);
+const POS_BOUND: U256 = U256::from_limbs([u64::MAX, u64::MAX, u64::MAX, i64::MAX as u64]);
+
+impl Sealed for U256 {}
+
+impl Biscuit for U256 {
+    const MAX_DIGITS: usize = U256::MAX_DIGITS;
+    const IS_SIGNED: bool = false;
+
+    #[inline]
+    fn parse_decimal_slice(u: &[u8]) -> Result<Self, ParseIntErr> {
+        Self::unsigned_decimal_core(u)
+    }
+}
+
+impl UnsignedCore for U256 {
+    /// The input is read in 16-digit chunks chained with `acc * 10^16 + chunk`,
+    /// the first chunk takes the remainder and is padded with b'0'.
+    fn unsigned_decimal_core(u: &[u8]) -> Result<Self, ParseIntErr> {
+        let length = u.len();
+        if length == 0 {
+            return Err(ParseIntErr::Empty);
+        }
+        if length > Self::MAX_DIGITS {
+            let start = u.iter().position(|&x| x != b'0').unwrap_or(length - 1);
+            if length - start > Self::MAX_DIGITS {
+                return Err(ParseIntErr::Overflow);
+            }
+            return Self::unsigned_decimal_core(&u[start..]);
+        }
+
+        let head = (length - 1) % 16 + 1;
+        let chunk = load_u128(&u[..head]);
+        if !check_decimal_bit_u128(chunk) {
+            return Err(ParseIntErr::NonDecimal);
+        }
+        let mut acc = U256::from(sixteen_to_u128(chunk));
+        for digits in u[head..].chunks_exact(16) {
+            let chunk = u128::from_le_bytes(digits.try_into().unwrap());
+            if !check_decimal_bit_u128(chunk) {
+                return Err(ParseIntErr::NonDecimal);
+            }
+            acc = acc
+                .checked_mul_add_u64(TEN_POW_16, sixteen_to_u128(chunk) as u64)
+                .ok_or(ParseIntErr::Overflow)?;
+        }
+        Ok(acc)
+    }
+}
+
+impl Sealed for I256 {}
+
+impl Biscuit for I256 {
+    const MAX_DIGITS: usize = I256::MAX_DIGITS;
+    const IS_SIGNED: bool = true;
+
+    #[inline]
+    fn parse_decimal_slice(u: &[u8]) -> Result<Self, ParseIntErr> {
+        match u {
+            [] | [b'-'] => Err(ParseIntErr::Empty),
+            [b'-', rest @ ..] => {
+                let abs = U256::unsigned_decimal_core(rest)
+                    .map_err(|err| if err == ParseIntErr::Overflow { ParseIntErr::NegOverflow } else { err })?;
+                if abs > NEG_BOUND {
+                    return Err(ParseIntErr::NegOverflow);
+                }
+                Ok(I256 { bits: abs.wrapping_neg() })
+            },
+            _ => {
+                let bits = U256::unsigned_decimal_core(u)?;
+                if bits > POS_BOUND {
+                    return Err(ParseIntErr::Overflow);
+                }
+                Ok(I256 { bits })
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEN_POW_19: u64 = 10_000_000_000_000_000_000;
+
+    #[test]
+    fn test_u128_range() {
+        for x in [0u128, 1, 9, 10, 9_999_999_999_999_999, 10_000_000_000_000_000, u64::MAX as u128, u128::MAX] {
+            let parsed = U256::parse_decimal(x.to_string().as_bytes()).unwrap();
+            assert_eq!(parsed, U256::from(x));
+            assert_eq!(u128::try_from(parsed), Ok(x));
+        }
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let (quotient, rem) = U256::from(u128::MAX).div_rem_u64(TEN_POW_19);
+        assert_eq!(quotient, U256::from(u128::MAX / TEN_POW_19 as u128));
+        assert_eq!(rem as u128, u128::MAX % TEN_POW_19 as u128);
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(U256::from_limbs([0, 0, 0, 1]) > U256::from_limbs([u64::MAX, u64::MAX, u64::MAX, 0]));
+        assert!(I256::MIN < I256::from(-1i64));
+        assert!(I256::from(-1i64) < I256::ZERO);
+        assert!(I256::ZERO < I256::MAX);
+    }
+
+    #[test]
+    fn test_display() {
+        for x in [0u128, 7, 9_999_999_999_999_999, 10_000_000_000_000_000, 10_000_000_000_000_001, u128::MAX] {
+            assert_eq!(U256::from(x).to_string(), x.to_string());
+            assert_eq!(format!("{:>45}", U256::from(x)), format!("{:>45}", x));
+            assert_eq!(format!("{:+}", U256::from(x)), format!("{:+}", x));
+        }
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(U256::MAX.to_string(), max);
+        assert_eq!(format!("{:>80}", U256::MAX), format!("{:>80}", max));
+        for x in [-1i128, -10_000_000_000_000_000, i128::MIN, i128::MAX] {
+            let wide = I256::parse_decimal(x.to_string().as_bytes()).unwrap();
+            assert_eq!(wide.to_string(), x.to_string());
+            assert_eq!(format!("{:08}", wide), format!("{:08}", x));
+            assert_eq!(format!("{:+}", wide), format!("{:+}", x));
+        }
+        assert_eq!(I256::MIN.to_string(), format!("-{}", "57896044618658097711785492504343953926634992332820282019728792003956564819968"));
+    }
+}
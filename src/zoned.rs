@@ -0,0 +1,146 @@
+//! Zoned decimal with an ASCII overpunch or a trailing separate sign
+//!
+//! The last byte carries the sign: `{` and `A`–`I` are the digits 0–9 of a positive
+//! value, `}` and `J`–`R` those of a negative one, and a plain digit is unsigned. A
+//! trailing `+` or `-` is a separate sign. The leading digits go through the same unsigned
+//! core as [`Biscuit::parse_decimal`](crate::Biscuit::parse_decimal), the last digit and the
+//! sign are folded in through [`SignMagnitude`].
+//!
+//! ```rust
+//! use biscuit_converter::zoned::parse_zoned_decimal;
+//! use biscuit_converter::error::ParseIntErr;
+//!
+//! assert_eq!(parse_zoned_decimal::<i32>(b"0012C"), Ok(123));
+//! assert_eq!(parse_zoned_decimal::<i32>(b"0012L"), Ok(-123));
+//! assert_eq!(parse_zoned_decimal::<i64>(b"100}"), Ok(-1000));
+//! assert_eq!(parse_zoned_decimal::<i16>(b"123-"), Ok(-123));
+//! assert_eq!(parse_zoned_decimal::<u16>(b"0042"), Ok(42));
+//! assert_eq!(parse_zoned_decimal::<u16>(b"004K"), Err(ParseIntErr::NonDecimal));
+//! ```
+use crate::SignMagnitude;
+use crate::error::ParseIntErr;
+use crate::unsigned_decimal::UnsignedCore;
+
+/// Parses a zoned decimal field, the sign is taken from its last byte
+///
+/// As with `-` in decimal text, a negative value is `NonDecimal` for unsigned types, and a
+/// field with more significant digits than `T::MAX_DIGITS` is `Overflow` (or `NegOverflow`)
+/// before its bytes are validated.
+pub fn parse_zoned_decimal<T: SignMagnitude>(u: &[u8]) -> Result<T, ParseIntErr> {
+    let Some((&last, init)) = u.split_last() else {
+        return Err(ParseIntErr::Empty);
+    };
+    let (neg, last_digit) = match last {
+        b'0'..=b'9' => (false, Some(last - b'0')),
+        b'{' => (false, Some(0)),
+        b'A'..=b'I' => (false, Some(last - b'A' + 1)),
+        b'}' => (true, Some(0)),
+        b'J'..=b'R' => (true, Some(last - b'J' + 1)),
+        b'+' => (false, None),
+        b'-' => (true, None),
+        _ => return Err(ParseIntErr::NonDecimal),
+    };
+    if neg && !T::IS_SIGNED {
+        return Err(ParseIntErr::NonDecimal);
+    }
+    if init.is_empty() && last_digit.is_none() {
+        return Err(ParseIntErr::Empty);
+    }
+
+    let zeros = init.iter().take_while(|&&b| b == b'0').count();
+    let digits = &init[zeros..];
+    if digits.len() + last_digit.is_some() as usize > T::MAX_DIGITS {
+        return Err(if neg { ParseIntErr::NegOverflow } else { ParseIntErr::Overflow });
+    }
+    // the sign only comes from the last byte, a `-` in the digits is rejected by the core
+    let lead = if digits.is_empty() { 0 } else { u128::unsigned_decimal_core(digits)? };
+    let magnitude = match last_digit {
+        Some(digit) => lead.checked_mul(10).and_then(|val| val.checked_add(digit as u128)),
+        None => Some(lead),
+    };
+    T::from_sign_magnitude(neg, magnitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Biscuit;
+
+    // overpunches the last digit of the decimal text
+    fn overpunch(val: i128) -> Vec<u8> {
+        let mut text = val.unsigned_abs().to_string().into_bytes();
+        let last = text.last_mut().unwrap();
+        *last = match (val < 0, *last) {
+            (false, b'0') => b'{',
+            (true, b'0') => b'}',
+            (false, digit) => digit - b'1' + b'A',
+            (true, digit) => digit - b'1' + b'J',
+        };
+        text
+    }
+
+    #[test]
+    fn test_against_std() {
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        for _ in 0..10_000 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let x = ((seed as i128) << 64 | seed as i128) >> (seed % 127);
+            assert_eq!(parse_zoned_decimal::<i128>(&overpunch(x)), Ok(x));
+            let separate = format!("{}{}", x.unsigned_abs(), if x < 0 { '-' } else { '+' });
+            assert_eq!(parse_zoned_decimal::<i128>(separate.as_bytes()), Ok(x));
+            assert_eq!(parse_zoned_decimal::<i32>(&overpunch(x)), i32::parse_decimal(x.to_string().as_bytes()));
+            assert_eq!(parse_zoned_decimal::<u64>(x.unsigned_abs().to_string().as_bytes()), u64::parse_decimal(x.unsigned_abs().to_string().as_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_every_overpunch() {
+        for (i, (positive, negative)) in b"{ABCDEFGHI".iter().zip(b"}JKLMNOPQR").enumerate() {
+            assert_eq!(parse_zoned_decimal::<i8>(&[b'1', *positive]), Ok(10 + i as i8));
+            assert_eq!(parse_zoned_decimal::<i8>(&[b'1', *negative]), Ok(-10 - i as i8));
+            assert_eq!(parse_zoned_decimal::<u8>(&[b'1', *positive]), Ok(10 + i as u8));
+            assert_eq!(parse_zoned_decimal::<u8>(&[b'1', *negative]), Err(ParseIntErr::NonDecimal));
+        }
+        assert_eq!(parse_zoned_decimal::<i8>(b"}"), Ok(0));
+        assert_eq!(parse_zoned_decimal::<i8>(b"0-"), Ok(0));
+        assert_eq!(parse_zoned_decimal::<i8>(b"000+"), Ok(0));
+    }
+
+    #[test]
+    fn test_bounds() {
+        assert_eq!(parse_zoned_decimal::<i8>(b"12Q"), Ok(-128));
+        assert_eq!(parse_zoned_decimal::<i8>(b"12R"), Err(ParseIntErr::NegOverflow));
+        assert_eq!(parse_zoned_decimal::<i8>(b"12G"), Ok(127));
+        assert_eq!(parse_zoned_decimal::<i8>(b"12H"), Err(ParseIntErr::Overflow));
+        assert_eq!(parse_zoned_decimal::<u128>(b"34028236692093846346337460743176821145E"), Ok(u128::MAX));
+
+        let mut long = vec![b'0'; 100];
+        long.extend_from_slice(b"17039411287477011272641015451212345R");
+        assert_eq!(parse_zoned_decimal::<i128>(&long), Ok(-170394112874770112726410154512123459));
+        long[0] = b'1';
+        assert_eq!(parse_zoned_decimal::<i128>(&long), Err(ParseIntErr::NegOverflow));
+        assert_eq!(parse_zoned_decimal::<u128>(&long), Err(ParseIntErr::NonDecimal));
+        *long.last_mut().unwrap() = b'I';
+        assert_eq!(parse_zoned_decimal::<u128>(&long), Err(ParseIntErr::Overflow));
+        // too long is reported before validation
+        long[1] = b' ';
+        assert_eq!(parse_zoned_decimal::<u128>(&long), Err(ParseIntErr::Overflow));
+        assert_eq!(parse_zoned_decimal::<i32>(b"12345678 0C"), Err(ParseIntErr::Overflow));
+        assert_eq!(parse_zoned_decimal::<i32>(b"12345678 0L"), Err(ParseIntErr::NegOverflow));
+        assert_eq!(parse_zoned_decimal::<i32>(b"1234567 0L"), Err(ParseIntErr::NonDecimal));
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert_eq!(parse_zoned_decimal::<i32>(b""), Err(ParseIntErr::Empty));
+        assert_eq!(parse_zoned_decimal::<i32>(b"-"), Err(ParseIntErr::Empty));
+        assert_eq!(parse_zoned_decimal::<i32>(b"+"), Err(ParseIntErr::Empty));
+        assert_eq!(parse_zoned_decimal::<i32>(b"12S"), Err(ParseIntErr::NonDecimal));
+        assert_eq!(parse_zoned_decimal::<i32>(b"1 2C"), Err(ParseIntErr::NonDecimal));
+        assert_eq!(parse_zoned_decimal::<i32>(b"-12C"), Err(ParseIntErr::NonDecimal));
+        assert_eq!(parse_zoned_decimal::<i32>(b"12--"), Err(ParseIntErr::NonDecimal));
+        assert_eq!(parse_zoned_decimal::<i32>(b"12a"), Err(ParseIntErr::NonDecimal));
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    use biscuit_converter::{Biscuit, ParseDecimalExt, parse};
+    use biscuit_converter::error::ParseIntErr;
+    use biscuit_converter::wide::{U256, I256};
+    use anyhow::Result;
+    use std::num::{NonZeroI16, NonZeroU32};
+
+    fn max_digits<T: Biscuit>() -> (usize, bool) {
+        (T::MAX_DIGITS, T::IS_SIGNED)
+    }
+
+    #[test]
+    fn test_associated_constants() -> Result<()> {
+        assert_eq!(max_digits::<u8>(), (u8::MAX.to_string().len(), false));
+        assert_eq!(max_digits::<u16>(), (u16::MAX.to_string().len(), false));
+        assert_eq!(max_digits::<u32>(), (u32::MAX.to_string().len(), false));
+        assert_eq!(max_digits::<u64>(), (u64::MAX.to_string().len(), false));
+        assert_eq!(max_digits::<u128>(), (u128::MAX.to_string().len(), false));
+        assert_eq!(max_digits::<i8>(), (i8::MAX.to_string().len(), true));
+        assert_eq!(max_digits::<i16>(), (i16::MAX.to_string().len(), true));
+        assert_eq!(max_digits::<i32>(), (i32::MAX.to_string().len(), true));
+        assert_eq!(max_digits::<i64>(), (i64::MAX.to_string().len(), true));
+        assert_eq!(max_digits::<i128>(), (i128::MAX.to_string().len(), true));
+        assert_eq!(max_digits::<U256>(), (U256::MAX.to_string().len(), false));
+        assert_eq!(max_digits::<I256>(), (I256::MAX.to_string().len(), true));
+        assert_eq!(max_digits::<NonZeroU32>(), (10, false));
+        assert_eq!(max_digits::<NonZeroI16>(), (5, true));
+        Ok(())
+    }
+
+    #[test]
+    fn test_free_function_and_extension() -> Result<()> {
+        assert_eq!(parse::<u32>(b"123"), Ok(123));
+        assert_eq!(parse::<i64>(b"-9223372036854775808"), Ok(i64::MIN));
+        assert_eq!(parse::<NonZeroU32>(b"0"), Err(ParseIntErr::Zero));
+        assert_eq!(b"123".parse_decimal::<u32>(), Ok(123));
+        assert_eq!(b"-12".parse_decimal::<u32>(), Err(ParseIntErr::NonDecimal));
+        assert_eq!(b"-12".try_parse_decimal::<i8>(), Some(-12));
+
+        let line = b"id=0042";
+        assert_eq!(line[3..].parse_decimal::<u16>(), Ok(42));
+        Ok(())
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_core() -> Result<()> {
+        assert_eq!(u8::unsinged_decimal_core(b"200", false, true), Err(ParseIntErr::Overflow));
+        assert_eq!(u8::unsinged_decimal_core(b"129", true, false), Err(ParseIntErr::NegOverflow));
+        assert_eq!(u64::unsinged_decimal_core(b"1234", false, false), Ok(1234));
+        assert_eq!(i32::unsinged_decimal_core(b"-1234", false, false), Ok(-1234));
+        // signed types ignore both flags
+        assert_eq!(i8::unsinged_decimal_core(b"-128", false, true), Ok(i8::MIN));
+        assert_eq!(i8::unsinged_decimal_core(b"127", true, false), Ok(i8::MAX));
+        assert_eq!(i8::unsinged_decimal_core(b"128", true, true), Err(ParseIntErr::Overflow));
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use biscuit_converter::Biscuit;
+    use biscuit_converter::error::ParseIntErr;
+    use anyhow::Result;
+
+    #[test]
+    fn test_canonical() -> Result<()> {
+        for i in -100_000..=100_000 {
+            let x = i.to_string();
+            assert_eq!(i32::parse_decimal_canonical(x.as_bytes()), Ok(i), "Failed for {}", i);
+            assert_eq!(i64::parse_decimal_canonical(x.as_bytes()), Ok(i as i64), "Failed for {}", i);
+        }
+        assert_eq!(u64::parse_decimal_canonical(b"0"), Ok(0));
+        assert_eq!(u128::parse_decimal_canonical(b"340282366920938463463374607431768211455"), Ok(u128::MAX));
+        assert_eq!(i128::parse_decimal_canonical(b"-170141183460469231731687303715884105728"), Ok(i128::MIN));
+        Ok(())
+    }
+
+    #[test]
+    fn test_non_canonical() -> Result<()> {
+        assert_eq!(u32::parse_decimal_canonical(b"00"), Err(ParseIntErr::LeadingZero));
+        assert_eq!(u32::parse_decimal_canonical(b"0123"), Err(ParseIntErr::LeadingZero));
+        assert_eq!(u64::parse_decimal_canonical(b"0000000000000000000000000000001"), Err(ParseIntErr::LeadingZero));
+        assert_eq!(i64::parse_decimal_canonical(b"-0"), Err(ParseIntErr::NegativeZero));
+        assert_eq!(i64::parse_decimal_canonical(b"-00"), Err(ParseIntErr::LeadingZero));
+        assert_eq!(i64::parse_decimal_canonical(b"-01"), Err(ParseIntErr::LeadingZero));
+        assert_eq!(i128::parse_decimal_canonical(b"-0"), Err(ParseIntErr::NegativeZero));
+
+        // the lenient parser still accepts them
+        assert_eq!(u32::parse_decimal(b"0123"), Ok(123));
+        assert_eq!(i64::parse_decimal(b"-0"), Ok(0));
+
+        // other errors take precedence
+        assert_eq!(u32::parse_decimal_canonical(b"012a"), Err(ParseIntErr::NonDecimal));
+        assert_eq!(u64::parse_decimal_canonical(b"-0"), Err(ParseIntErr::NonDecimal));
+        assert_eq!(u8::parse_decimal_canonical(b""), Err(ParseIntErr::Empty));
+        Ok(())
+    }
+}
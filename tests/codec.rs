@@ -0,0 +1,56 @@
+#![cfg(feature = "tokio-util")]
+
+#[cfg(test)]
+mod tests {
+    use biscuit_converter::codec::{DecimalCodecErr, DecimalLineCodec};
+    use biscuit_converter::error::ParseIntErr;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::io::AsyncWriteExt;
+    use tokio_util::codec::{FramedRead, FramedWrite};
+    use anyhow::Result;
+
+    #[tokio::test]
+    async fn test_duplex_round_trip() -> Result<()> {
+        // a small duplex buffer makes frames cross reads
+        let (client, server) = tokio::io::duplex(7);
+        let values: Vec<i64> = (0..1_000).map(|i| (i - 500) * 1_000_000_007).collect();
+
+        let sent = values.clone();
+        let writer = tokio::spawn(async move {
+            let mut framed = FramedWrite::new(client, DecimalLineCodec::<i64>::new());
+            for val in sent {
+                framed.send(val).await?;
+            }
+            framed.close().await?;
+            Ok::<_, DecimalCodecErr>(())
+        });
+
+        let mut framed = FramedRead::new(server, DecimalLineCodec::<i64>::new());
+        let mut received = Vec::new();
+        while let Some(val) = framed.next().await {
+            received.push(val?);
+        }
+        writer.await??;
+        assert_eq!(received, values);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_duplex_errors() -> Result<()> {
+        let (mut client, server) = tokio::io::duplex(64);
+        client.write_all(b"17\r\n4294967296\n").await?;
+        drop(client);
+
+        let mut framed = FramedRead::new(server, DecimalLineCodec::<u32>::new_with_max_length(10));
+        assert_eq!(framed.next().await.unwrap()?, 17);
+        assert!(matches!(framed.next().await, Some(Err(DecimalCodecErr::Parse(ParseIntErr::Overflow)))));
+
+        let (mut client, server) = tokio::io::duplex(64);
+        client.write_all(b"12345678901\n").await?;
+        drop(client);
+
+        let mut framed = FramedRead::new(server, DecimalLineCodec::<u64>::new_with_max_length(10));
+        assert!(matches!(framed.next().await, Some(Err(DecimalCodecErr::MaxLengthExceeded))));
+        Ok(())
+    }
+}
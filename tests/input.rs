@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use biscuit_converter::{Biscuit, ParseDecimalExt, parse};
+    use biscuit_converter::error::ParseIntErr;
+    use anyhow::Result;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_containers() -> Result<()> {
+        let s = String::from("-12345");
+        assert_eq!(i32::parse_decimal("-12345"), Ok(-12345));
+        assert_eq!(i32::parse_decimal(&s), Ok(-12345));
+        assert_eq!(i32::parse_decimal(s.as_str()), Ok(-12345));
+        assert_eq!(i32::parse_decimal(&Cow::Borrowed(s.as_str())), Ok(-12345));
+        assert_eq!(i32::parse_decimal(&Cow::<str>::Owned(s.clone())), Ok(-12345));
+        assert_eq!(i32::parse_decimal(&Cow::Borrowed(s.as_bytes())), Ok(-12345));
+        assert_eq!(i32::parse_decimal(&s.clone().into_bytes()), Ok(-12345));
+        assert_eq!(i32::parse_decimal(&s.clone().into_bytes().into_boxed_slice()), Ok(-12345));
+        assert_eq!(i32::parse_decimal(&&s), Ok(-12345));
+
+        // 0.3 call sites: `&Vec<u8>` still compiles, deref-only containers go through `&x[..]`
+        let v: Vec<u8> = s.clone().into_bytes();
+        let rc: std::rc::Rc<[u8]> = v.clone().into();
+        assert_eq!(i32::parse_decimal(&v), Ok(-12345));
+        assert_eq!(i32::parse_decimal(&rc[..]), Ok(-12345));
+
+        assert_eq!(u8::try_parse_decimal("256"), None);
+        assert_eq!(u8::parse_decimal_canonical("007"), Err(ParseIntErr::LeadingZero));
+        assert_eq!(parse::<u64>(&s[1..]), Ok(12345));
+        assert_eq!(s.parse_decimal::<i64>(), Ok(-12345));
+        assert_eq!(s.as_bytes().try_parse_decimal::<u64>(), None);
+        Ok(())
+    }
+
+    macro_rules! check_array {
+        ($t:ty, $($n:literal)*) => {
+            $(
+                for digit in [b'0', b'1', b'9', b'a'] {
+                    let array = [digit; $n];
+                    assert_eq!(<$t>::parse_decimal(&array), <$t>::parse_decimal(&array[..]), "Failed for {:?}", array);
+                    assert_eq!(array.parse_decimal::<$t>(), <$t>::parse_decimal(&array[..]), "Failed for {:?}", array);
+                }
+            )*
+        };
+    }
+
+    #[test]
+    fn test_fixed_size_arrays() -> Result<()> {
+        check_array!(u8, 0 1 2 3 4 5);
+        check_array!(u16, 0 1 4 5 6);
+        check_array!(u32, 0 1 7 9 10 11);
+        check_array!(u64, 0 1 8 16 19 20 21 32);
+        check_array!(u128, 0 1 16 17 33 38 39 40 64);
+        check_array!(i64, 0 1 8 19 20);
+
+        assert_eq!(u64::parse_decimal(b"18446744073709551615"), Ok(u64::MAX));
+        assert_eq!(u64::parse_decimal(b"18446744073709551616"), Err(ParseIntErr::Overflow));
+        assert_eq!(u8::parse_decimal(b"00000255"), Ok(255));
+        assert_eq!(u8::parse_decimal(b""), Err(ParseIntErr::Empty));
+        assert_eq!(u16::parse_decimal(b"-1"), Err(ParseIntErr::NonDecimal));
+        Ok(())
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn test_bytes() -> Result<()> {
+        let frame = bytes::Bytes::from_static(b"4200|17");
+        assert_eq!(u32::parse_decimal(&frame.slice(..4)), Ok(4200));
+        assert_eq!(frame.slice(5..).parse_decimal::<u8>(), Ok(17));
+        let buf = bytes::BytesMut::from(&b"-99"[..]);
+        assert_eq!(i16::parse_decimal(&buf), Ok(-99));
+        Ok(())
+    }
+}
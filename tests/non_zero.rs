@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use biscuit_converter::Biscuit;
+    use biscuit_converter::error::ParseIntErr;
+    use anyhow::Result;
+    use std::num::{NonZeroU8, NonZeroU64, NonZeroU128, NonZeroI8, NonZeroI32, NonZeroI128};
+
+    #[test]
+    fn test_non_zero_unsigned() -> Result<()> {
+        assert_eq!(NonZeroU64::parse_decimal(b"1"), Ok(NonZeroU64::new(1).unwrap()));
+        assert_eq!(NonZeroU64::parse_decimal(b"18446744073709551615"), Ok(NonZeroU64::MAX));
+        assert_eq!(NonZeroU8::parse_decimal(b"0255"), Ok(NonZeroU8::MAX));
+        assert_eq!(NonZeroU128::parse_decimal(b"0000000000000000000000000000000000000000042"), Ok(NonZeroU128::new(42).unwrap()));
+
+        for length in 1..=64 {
+            let zeros = vec![b'0'; length];
+            assert_eq!(NonZeroU8::parse_decimal(&zeros), Err(ParseIntErr::Zero), "Failed for {} zeros", length);
+            assert_eq!(NonZeroU64::parse_decimal(&zeros), Err(ParseIntErr::Zero), "Failed for {} zeros", length);
+            assert_eq!(NonZeroU128::parse_decimal(&zeros), Err(ParseIntErr::Zero), "Failed for {} zeros", length);
+            assert_eq!(NonZeroU64::try_parse_decimal(&zeros), None);
+        }
+
+        assert_eq!(NonZeroU64::parse_decimal(b""), Err(ParseIntErr::Empty));
+        assert_eq!(NonZeroU64::parse_decimal(b"0a"), Err(ParseIntErr::NonDecimal));
+        assert_eq!(NonZeroU8::parse_decimal(b"256"), Err(ParseIntErr::Overflow));
+        Ok(())
+    }
+
+    #[test]
+    fn test_non_zero_signed() -> Result<()> {
+        assert_eq!(NonZeroI8::parse_decimal(b"-128"), Ok(NonZeroI8::MIN));
+        assert_eq!(NonZeroI32::parse_decimal(b"-1"), Ok(NonZeroI32::new(-1).unwrap()));
+        assert_eq!(NonZeroI128::parse_decimal(b"170141183460469231731687303715884105727"), Ok(NonZeroI128::MAX));
+        assert_eq!(NonZeroI32::parse_decimal(b"0"), Err(ParseIntErr::Zero));
+        assert_eq!(NonZeroI32::parse_decimal(b"-0"), Err(ParseIntErr::Zero));
+        assert_eq!(NonZeroI32::parse_decimal(b"-000000000000000000000"), Err(ParseIntErr::Zero));
+        assert_eq!(NonZeroI8::parse_decimal(b"-129"), Err(ParseIntErr::NegOverflow));
+        assert_eq!(NonZeroI32::try_parse_decimal(b"-0"), None);
+        assert_eq!(NonZeroI32::parse_decimal_canonical(b"07"), Err(ParseIntErr::LeadingZero));
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+// regressions of the unsigned overflow checks at the maximum number of digits,
+// where the upper chunk used to be multiplied with wrapping arithmetic
+#[cfg(test)]
+mod tests {
+    use biscuit_converter::Biscuit;
+    use biscuit_converter::error::ParseIntErr;
+    use anyhow::Result;
+
+    #[test]
+    fn test_u8_overflow() -> Result<()> {
+        for i in 256..=999 {
+            let x = i.to_string();
+            assert_eq!(u8::parse_decimal(x.as_bytes()), Err(ParseIntErr::Overflow), "Failed for {}", i);
+        }
+        assert_eq!(u8::parse_decimal(b"255"), Ok(u8::MAX));
+        Ok(())
+    }
+
+    #[test]
+    fn test_u16_overflow() -> Result<()> {
+        for i in 65536..=99999 {
+            let x = i.to_string();
+            assert_eq!(u16::parse_decimal(x.as_bytes()), Err(ParseIntErr::Overflow), "Failed for {}", i);
+        }
+        assert_eq!(u16::parse_decimal(b"65535"), Ok(u16::MAX));
+        Ok(())
+    }
+
+    #[test]
+    fn test_u32_overflow() -> Result<()> {
+        for x in ["4294967296", "5000000000", "9999999999", "42949672950"] {
+            assert_eq!(u32::parse_decimal(x.as_bytes()), Err(ParseIntErr::Overflow), "Failed for {}", x);
+        }
+        assert_eq!(u32::parse_decimal(b"4294967295"), Ok(u32::MAX));
+        Ok(())
+    }
+
+    #[test]
+    fn test_u64_overflow() -> Result<()> {
+        for x in ["18446744073709551616", "50000000000000000000", "99999999999999999999"] {
+            assert_eq!(u64::parse_decimal(x.as_bytes()), Err(ParseIntErr::Overflow), "Failed for {}", x);
+        }
+        assert_eq!(u64::parse_decimal(b"18446744073709551615"), Ok(u64::MAX));
+        Ok(())
+    }
+
+    #[test]
+    fn test_u128_overflow() -> Result<()> {
+        for x in ["340282366920938463463374607431768211456", "999999999999999999999999999999999999999"] {
+            assert_eq!(u128::parse_decimal(x.as_bytes()), Err(ParseIntErr::Overflow), "Failed for {}", x);
+        }
+        assert_eq!(u128::parse_decimal(b"340282366920938463463374607431768211455"), Ok(u128::MAX));
+        Ok(())
+    }
+}
@@ -1,6 +1,5 @@
 #[cfg(test)]
 mod tests {
-    use std::i128;
 
     use biscuit_converter::Biscuit;
     use biscuit_converter::error::ParseIntErr;
@@ -29,7 +28,7 @@ mod tests {
         assert_eq!(i128::parse_decimal(empty), Err(ParseIntErr::Empty), "Failed for empty input");
 
         // Test single zero
-        let single_zero: &[u8] = &[b'0'];
+        let single_zero: &[u8] = b"0";
         assert_eq!(i128::parse_decimal(single_zero), Ok(0), "Failed for single zero");
 
         for i in 1..I128_LENGTH_BOUND {
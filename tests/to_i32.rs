@@ -91,4 +91,23 @@ mod tests {
         assert_eq!(val_leading_zeros_neg, Ok(-1234567890));
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_against_std() -> Result<()> {
+        for length in 1..=24 {
+            let digits: String = "2147483648".chars().cycle().take(length).collect();
+            for input in [digits.clone(), format!("-{}", digits), format!("-000000{}", digits)] {
+                let expected = match input.parse::<i32>() {
+                    Ok(val) => Ok(val),
+                    Err(e) => match e.kind() {
+                        std::num::IntErrorKind::PosOverflow => Err(ParseIntErr::Overflow),
+                        std::num::IntErrorKind::NegOverflow => Err(ParseIntErr::NegOverflow),
+                        kind => panic!("unexpected {:?}", kind),
+                    },
+                };
+                assert_eq!(i32::parse_decimal(input.as_bytes()), expected, "Failed for {}", input);
+            }
+        }
+        Ok(())
+    }
+}
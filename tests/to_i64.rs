@@ -11,9 +11,9 @@ mod tests {
             let x = i.to_string();
             let x_byte: &[u8] = x.as_bytes();
             let val = i64::parse_decimal(x_byte).unwrap();
-            if i >= 0 && (i as u64) <= u64::MAX {
+            if i >= 0 {
                 assert_eq!(
-                    val, i as i64,
+                    val, i,
                     "Failed for positive {}", i
                 );
             } else {
@@ -33,7 +33,7 @@ mod tests {
         assert_eq!(i64::parse_decimal(empty), Err(ParseIntErr::Empty), "Failed for empty input");
 
         // Test single zero
-        let single_zero: &[u8] = &[b'0'];
+        let single_zero: &[u8] = b"0";
         assert_eq!(i64::parse_decimal(single_zero), Ok(0), "Failed for single zero");
 
         for i in 1..I64_LENGTH_BOUND {
@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use biscuit_converter::Biscuit;
+    use biscuit_converter::error::ParseIntErr;
+    use anyhow::Result;
+
+    #[test]
+    fn test_back_and_forth() -> Result<()> {
+        for i in i8::MIN..=i8::MAX {
+            let x = i.to_string();
+            let x_byte: &[u8] = x.as_bytes();
+            let val = i8::parse_decimal(x_byte);
+            assert_eq!(val, Ok(i), "Failed for {}", i);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_i8_extremes() -> Result<()> {
+        assert_eq!(i8::parse_decimal(b"128"), Err(ParseIntErr::Overflow));
+        assert_eq!(i8::parse_decimal(b"-129"), Err(ParseIntErr::NegOverflow));
+        assert_eq!(i8::parse_decimal(b"999"), Err(ParseIntErr::Overflow));
+        assert_eq!(i8::parse_decimal(b"-9999"), Err(ParseIntErr::NegOverflow));
+        assert_eq!(i8::parse_decimal(b"-0000000000000128"), Ok(i8::MIN));
+        assert_eq!(i8::parse_decimal(b"-00000000000000000000128"), Ok(i8::MIN));
+        assert_eq!(i8::parse_decimal(b"-00000000000000000000129"), Err(ParseIntErr::NegOverflow));
+        Ok(())
+    }
+
+    #[test]
+    fn test_i8_sign() -> Result<()> {
+        assert_eq!(i8::parse_decimal(b"-"), Err(ParseIntErr::Empty));
+        assert_eq!(i8::parse_decimal(b"-0"), Ok(0));
+        assert_eq!(i8::parse_decimal(b"--1"), Err(ParseIntErr::NonDecimal));
+        assert_eq!(i8::parse_decimal(b"1-"), Err(ParseIntErr::NonDecimal));
+        assert_eq!(i8::parse_decimal(b"+1"), Err(ParseIntErr::NonDecimal));
+        Ok(())
+    }
+}
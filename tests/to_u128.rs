@@ -54,7 +54,7 @@ mod tests {
         
         let byte_test = u128::MAX.to_string();
         let byte_test = byte_test.as_bytes();
-        let x: &[u8] = &byte_test[..];
+        let x: &[u8] = byte_test;
         let val = u128::parse_decimal(x).unwrap();
         assert_eq!(val, u128::MAX);
 
@@ -74,6 +74,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::inconsistent_digit_grouping)]
     fn test_leading_zero_u128() -> Result<()> {
         
         let byte_leading_zeros = b"00000000000000000000000123456789012345678901234000000";
@@ -65,7 +65,7 @@ mod tests {
         let byte_test_n1_string = (u16::MAX - 1).to_string();
         let byte_test_n1: &[u8] = byte_test_n1_string.as_bytes();
         let x_p1: &[u8] = &byte_test_p1[..];
-        let x_n1: &[u8] = &byte_test_n1[..];
+        let x_n1: &[u8] = byte_test_n1;
         let val_p1 = u16::parse_decimal(x_p1);
         let val_n1 = u16::parse_decimal(x_n1);
         assert_eq!(val_p1, Err(ParseIntErr::Overflow));
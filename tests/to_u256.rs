@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use biscuit_converter::Biscuit;
+    use biscuit_converter::error::ParseIntErr;
+    use biscuit_converter::wide::{U256, I256};
+    use anyhow::Result;
+
+    const U256_MAX: &str = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+    const I256_MAX: &str = "57896044618658097711785492504343953926634992332820282019728792003956564819967";
+    const I256_MIN: &str = "-57896044618658097711785492504343953926634992332820282019728792003956564819968";
+
+    #[test]
+    fn test_to_u256() -> Result<()> {
+        for length in 1..=U256::MAX_DIGITS {
+            for digit in [b'0', b'1', b'9'] {
+                let x = vec![digit; length];
+                let s = std::str::from_utf8(&x)?;
+                let val = U256::parse_decimal(&x);
+                if digit == b'0' {
+                    assert_eq!(val, Ok(U256::ZERO), "Failed for {}", s);
+                } else if digit == b'9' && length == U256::MAX_DIGITS {
+                    assert_eq!(val, Err(ParseIntErr::Overflow), "Failed for {}", s);
+                } else {
+                    assert_eq!(val?.to_string(), s, "Failed for {}", s);
+                }
+            }
+        }
+
+        assert_eq!(U256::parse_decimal(U256_MAX.as_bytes()), Ok(U256::MAX));
+        assert_eq!(U256::MAX.to_string(), U256_MAX);
+        assert_eq!(U256::parse_decimal(b"115792089237316195423570985008687907853269984665640564039457584007913129639936"), Err(ParseIntErr::Overflow));
+        assert_eq!(U256::parse_decimal(&[b'9'; 79]), Err(ParseIntErr::Overflow));
+        assert_eq!(U256::parse_decimal(format!("000{}", U256_MAX).as_bytes()), Ok(U256::MAX));
+        assert_eq!(U256::parse_decimal(&[b'0'; 100]), Ok(U256::ZERO));
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_u256_error() -> Result<()> {
+        assert_eq!(U256::parse_decimal(b""), Err(ParseIntErr::Empty));
+        assert_eq!(U256::parse_decimal(b"-1"), Err(ParseIntErr::NonDecimal));
+        for pos in 0..U256_MAX.len() {
+            let mut x = U256_MAX.as_bytes().to_vec();
+            x[pos] = b':';
+            assert_eq!(U256::parse_decimal(&x), Err(ParseIntErr::NonDecimal), "Failed for position {}", pos);
+            x[pos] = b'/';
+            assert_eq!(U256::parse_decimal(&x), Err(ParseIntErr::NonDecimal), "Failed for position {}", pos);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_i256() -> Result<()> {
+        assert_eq!(I256::parse_decimal(I256_MAX.as_bytes()), Ok(I256::MAX));
+        assert_eq!(I256::parse_decimal(I256_MIN.as_bytes()), Ok(I256::MIN));
+        assert_eq!(I256::MAX.to_string(), I256_MAX);
+        assert_eq!(I256::MIN.to_string(), I256_MIN);
+        assert_eq!(I256::parse_decimal(b"57896044618658097711785492504343953926634992332820282019728792003956564819968"), Err(ParseIntErr::Overflow));
+        assert_eq!(I256::parse_decimal(b"-57896044618658097711785492504343953926634992332820282019728792003956564819969"), Err(ParseIntErr::NegOverflow));
+        assert_eq!(I256::parse_decimal(format!("-{}", U256_MAX).as_bytes()), Err(ParseIntErr::NegOverflow));
+        assert_eq!(I256::parse_decimal(b"-"), Err(ParseIntErr::Empty));
+        assert_eq!(I256::parse_decimal(b"--1"), Err(ParseIntErr::NonDecimal));
+
+        for x in [0i128, 1, -1, 1234567890123456789, -1234567890123456789, i128::MIN, i128::MAX] {
+            let val = I256::parse_decimal(x.to_string().as_bytes())?;
+            assert_eq!(val, I256::from(x));
+            assert_eq!(val.to_string(), x.to_string());
+        }
+        Ok(())
+    }
+}
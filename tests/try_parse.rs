@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use biscuit_converter::Biscuit;
+    use anyhow::Result;
+
+    fn check_against_std<T>(input: &str)
+    where
+        T: Biscuit + std::str::FromStr + PartialEq + std::fmt::Debug,
+    {
+        let expected = input.parse::<T>().ok();
+        assert_eq!(T::try_parse_decimal(input.as_bytes()), expected, "Failed for {:?}", input);
+    }
+
+    fn check_all(input: &str) {
+        check_against_std::<u8>(input);
+        check_against_std::<u16>(input);
+        check_against_std::<u32>(input);
+        check_against_std::<u64>(input);
+        check_against_std::<u128>(input);
+        check_against_std::<i8>(input);
+        check_against_std::<i16>(input);
+        check_against_std::<i32>(input);
+        check_against_std::<i64>(input);
+        check_against_std::<i128>(input);
+    }
+
+    #[test]
+    fn test_boundaries() -> Result<()> {
+        let inputs = [
+            i8::MIN.to_string(), i8::MAX.to_string(), u8::MAX.to_string(), (u8::MAX as u16 + 1).to_string(),
+            i16::MIN.to_string(), i16::MAX.to_string(), u16::MAX.to_string(), (u16::MAX as u32 + 1).to_string(),
+            i32::MIN.to_string(), i32::MAX.to_string(), u32::MAX.to_string(), (u32::MAX as u64 + 1).to_string(),
+            i64::MIN.to_string(), i64::MAX.to_string(), u64::MAX.to_string(), (u64::MAX as u128 + 1).to_string(),
+            i128::MIN.to_string(), i128::MAX.to_string(), u128::MAX.to_string(),
+            "340282366920938463463374607431768211456".to_string(),
+            "-170141183460469231731687303715884105729".to_string(),
+        ];
+        for input in inputs.iter() {
+            check_all(input);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_lengths() -> Result<()> {
+        for length in 1..=45 {
+            let digits: String = "9876543210".chars().cycle().take(length).collect();
+            check_all(&digits);
+            check_all(&format!("-{}", digits));
+            check_all(&format!("{}x", &digits[1..]));
+            check_all(&format!("000000000000000000000000000000{}", digits));
+        }
+        for input in ["", "-", "0", "-0", " 1", "1 ", "--1"] {
+            check_all(input);
+        }
+        // unlike std, an explicit plus sign is not accepted
+        assert_eq!(u32::try_parse_decimal(b"+1"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_non_decimal_positions() -> Result<()> {
+        for length in 1..=39 {
+            let nines = "9".repeat(length);
+            check_all(&nines);
+            for pos in 0..length {
+                let mut u = nines.clone().into_bytes();
+                u[pos] = b':';
+                check_all(std::str::from_utf8(&u)?);
+            }
+        }
+        assert_eq!(u128::try_parse_decimal(b"9999999999999999999999999999999999999999"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_leading_zeros() {
+        assert_eq!(u8::try_parse_decimal(b"0000000255"), Some(255));
+        assert_eq!(u8::try_parse_decimal(b"0000000256"), None);
+        assert_eq!(u16::try_parse_decimal(b"0000000"), Some(0));
+        assert_eq!(u32::try_parse_decimal(b"000000000004294967295"), Some(u32::MAX));
+        assert_eq!(u64::try_parse_decimal(b"000000000000000000000000000001"), Some(1));
+        assert_eq!(u64::try_parse_decimal(b"00000000000000000000000000000a"), None);
+        assert_eq!(u128::try_parse_decimal(&[b'0'; 64]), Some(0));
+    }
+}
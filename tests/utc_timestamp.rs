@@ -0,0 +1,86 @@
+use biscuit_converter::timestamp::{
+    format_utc_timestamp,
+    parse_utc_timestamp,
+    Precision,
+    UTC_TIMESTAMP_MAX_LEN,
+};
+use biscuit_converter::error::TimeErr;
+use chrono::{DateTime, NaiveDateTime};
+
+const PRECISIONS: [(Precision, &str); 4] = [
+    (Precision::Seconds, "%Y%m%d-%H:%M:%S"),
+    (Precision::Millis, "%Y%m%d-%H:%M:%S%.3f"),
+    (Precision::Micros, "%Y%m%d-%H:%M:%S%.6f"),
+    (Precision::Nanos, "%Y%m%d-%H:%M:%S%.9f"),
+];
+
+// None when out of the range of i64 nanoseconds
+fn chrono_nanos(u: &str) -> Option<i64> {
+    NaiveDateTime::parse_from_str(u, "%Y%m%d-%H:%M:%S%.f")
+        .unwrap()
+        .and_utc()
+        .timestamp_nanos_opt()
+}
+
+// spread over the whole range of i64 nanoseconds, plus the ends of it
+fn samples() -> impl Iterator<Item = i64> {
+    let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+    let random = (0..20_000).map(move |_| {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed as i64
+    });
+    random.chain([0, -1, 1, i64::MIN, i64::MAX, 951_782_400_000_000_000, 4_107_456_000_123_456_789])
+}
+
+#[test]
+fn test_format_against_chrono() {
+    let mut buf = [0u8; UTC_TIMESTAMP_MAX_LEN];
+    for nanos in samples() {
+        let expected = DateTime::from_timestamp_nanos(nanos);
+        for (precision, layout) in PRECISIONS {
+            let formatted = format_utc_timestamp(nanos, precision, &mut buf);
+            assert_eq!(
+                std::str::from_utf8(formatted).unwrap(),
+                expected.format(layout).to_string(),
+                "Failed for {} at {:?}", nanos, precision,
+            );
+        }
+    }
+}
+
+#[test]
+fn test_parse_against_chrono() {
+    for nanos in samples() {
+        for (precision, layout) in PRECISIONS {
+            let formatted = DateTime::from_timestamp_nanos(nanos).format(layout).to_string();
+            assert_eq!(
+                parse_utc_timestamp(formatted.as_bytes()).ok(),
+                chrono_nanos(&formatted),
+                "Failed for {} at {:?}", formatted, precision,
+            );
+        }
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    let mut buf = [0u8; UTC_TIMESTAMP_MAX_LEN];
+    for nanos in samples() {
+        let formatted = format_utc_timestamp(nanos, Precision::Nanos, &mut buf);
+        assert_eq!(parse_utc_timestamp(formatted), Ok(nanos));
+
+        let millis = format_utc_timestamp(nanos, Precision::Millis, &mut buf);
+        // truncating i64::MIN leaves the range
+        let expected = nanos.checked_sub(nanos.rem_euclid(1_000_000)).ok_or(TimeErr::OutOfRange);
+        assert_eq!(parse_utc_timestamp(millis), expected);
+    }
+}
+
+#[test]
+fn test_leap_second_against_chrono() {
+    for u in ["20161231-23:59:60", "20150630-23:59:60.999", "19720630-23:59:60.000001", "20081231-23:59:60.123456789"] {
+        assert_eq!(parse_utc_timestamp(u.as_bytes()).ok(), chrono_nanos(u), "Failed for {}", u);
+    }
+}